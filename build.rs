@@ -0,0 +1,83 @@
+//! Collects all solutions in `src/bin` into a single module for the `all` binary.
+//!
+//! Each `{year}-{day}.rs` is copied to `OUT_DIR` with its `solution!` invocation switched to the
+//! registry variant, which exposes the parts as a `SOLUTION` constant instead of generating `main`.
+//!
+//! The version of the compiler cargo builds with is exposed as `AOC_RUSTC_VERSION`, for the benchmark
+//! environment. The `rustc` on the PATH may be a different toolchain, e.g. with rustup overrides.
+use std::{env, fs, path::Path, process::Command};
+
+const SOLUTION_MACRO: &str = "advent_of_code::solution!(";
+
+fn is_solution_file(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    name.len() == "2024-01.rs".len()
+        && name.ends_with(".rs")
+        && bytes[4] == b'-'
+        && bytes[..4]
+            .iter()
+            .chain(&bytes[5..7])
+            .all(u8::is_ascii_digit)
+}
+
+fn rustc_version() -> Option<String> {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string())
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!(
+        "cargo:rustc-env=AOC_RUSTC_VERSION={}",
+        rustc_version().unwrap_or_default()
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let solutions_dir = Path::new(&out_dir).join("solutions");
+    fs::create_dir_all(&solutions_dir).unwrap();
+
+    let mut names: Vec<String> = fs::read_dir("src/bin")
+        .unwrap()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| is_solution_file(name))
+        .collect();
+    names.sort_unstable();
+
+    let mut modules = String::from("// @generated by build.rs\n");
+    let mut entries = vec![];
+
+    for name in names {
+        let source = fs::read_to_string(Path::new("src/bin").join(&name)).unwrap();
+        if !source.contains(SOLUTION_MACRO) {
+            continue;
+        }
+
+        let target = solutions_dir.join(&name);
+        fs::write(
+            &target,
+            source.replacen(SOLUTION_MACRO, &format!("{SOLUTION_MACRO}@registry "), 1),
+        )
+        .unwrap();
+
+        let module = format!(
+            "solution_{}",
+            name.trim_end_matches(".rs").replace('-', "_")
+        );
+        modules.push_str(&format!("#[path = {:?}]\nmod {module};\n", target));
+        entries.push(format!("{module}::SOLUTION"));
+    }
+
+    modules.push_str(&format!(
+        "\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[{}];\n",
+        entries.join(", ")
+    ));
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), modules).unwrap();
+}
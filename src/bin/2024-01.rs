@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use num::abs;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2024, 1);
 
 fn parse(s: &str) -> (Vec<i32>, Vec<i32>) {
     s.lines()
@@ -37,20 +37,3 @@ pub fn part_two(input: &str) -> Option<u32> {
             .sum::<i32>() as u32,
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
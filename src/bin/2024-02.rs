@@ -1,7 +1,7 @@
 use rayon::prelude::*;
 use std::ops::RangeInclusive;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2024, 2);
 
 fn is_safe(line: &str, tolerate: bool) -> bool {
     let xs = line
@@ -48,20 +48,3 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     Some(solve(input, true))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
-}
@@ -2,7 +2,7 @@ use advent_of_code::char_grid::ByteGrid;
 use advent_of_code::vec2::{Vec2, DIRECTIONS};
 use itertools::Itertools;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2024, 4);
 
 fn contains_xmas(grid: &ByteGrid, p: &Vec2<i32>, d: &Vec2<i32>) -> bool {
     "XMAS"
@@ -47,20 +47,3 @@ pub fn part_two(input: &str) -> Option<u32> {
             .count() as u32,
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9));
-    }
-}
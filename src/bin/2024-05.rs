@@ -2,7 +2,7 @@ use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 use std::cmp::Ordering;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2024, 5);
 
 fn parse(input: &str) -> (OrderingRules, Vec<Vec<i32>>) {
     let (rules_str, updates_str) = input.split_once("\n\n").unwrap();
@@ -77,20 +77,3 @@ pub fn part_two(input: &str) -> Option<u32> {
             .sum::<i32>() as u32,
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
-    }
-}
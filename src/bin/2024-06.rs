@@ -6,9 +6,9 @@ use rayon::prelude::*;
 
 type Vec2 = advent_of_code::vec2::Vec2<i32>;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2024, 6);
 
-fn has_loop(grid: &ByteGrid, obstruction: &Vec2, start: Vec2) -> bool {
+fn has_loop(grid: &ByteGrid, start: Vec2) -> bool {
     let mut d = CardinalDirection::North;
     let mut p = start;
     let mut seen = HashSet::<(Vec2, CardinalDirection)>::new();
@@ -16,7 +16,7 @@ fn has_loop(grid: &ByteGrid, obstruction: &Vec2, start: Vec2) -> bool {
     while grid.contains(&p) {
         let mut next = p + d.to_vec();
 
-        while grid[&next] == b'#' || next == *obstruction {
+        while grid[&next] == b'#' {
             if !seen.insert((p, d)) {
                 return true;
             }
@@ -69,24 +69,17 @@ pub fn part_two(input: &str) -> Option<u32> {
             .iter()
             .collect_vec()
             .par_iter()
-            .filter(|&it| has_loop(&map, it, start))
+            // every thread writes the obstruction into a copy of its own, and removes it again after.
+            .map_init(
+                || map.clone(),
+                |grid, &p| {
+                    grid.set(p, b'#');
+                    let result = has_loop(grid, start);
+                    grid.set(p, b'.');
+                    result
+                },
+            )
+            .filter(|&has_loop| has_loop)
             .count() as u32,
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-}
@@ -1,6 +1,6 @@
 use rayon::prelude::*;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2024, 7);
 
 fn is_satisfiable(total: u64, xs: &[u64], part2: bool) -> bool {
     fn recurse<I: Iterator<Item = u64> + Clone>(
@@ -51,20 +51,3 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     solve(input, true)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
-}
@@ -5,7 +5,7 @@ use std::ops::RangeInclusive;
 type Vec2 = advent_of_code::vec2::Vec2<i16>;
 type Bounds = advent_of_code::bounds::Bounds<i16>;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2024, 8);
 
 fn antinodes(
     antennas: &[(char, Vec2)],
@@ -69,20 +69,3 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     Some(solve(input, 0..=i16::MAX))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
-    }
-}
@@ -1,9 +1,9 @@
 use itertools::Itertools;
-use std::iter::repeat;
+use std::iter::repeat_n;
 use std::ops::Range;
 use BlockReservation::*;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2024, 9);
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum BlockReservation {
@@ -136,8 +136,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut buffer = Vec::<BlockReservation>::new();
 
     for (id, blocks, free_blocks) in parse(input) {
-        buffer.extend(repeat(FileBlock(id)).take(blocks as usize));
-        buffer.extend(repeat(FreeBlock).take(free_blocks as usize));
+        buffer.extend(repeat_n(FileBlock(id), blocks as usize));
+        buffer.extend(repeat_n(FreeBlock, free_blocks as usize));
     }
 
     Some(defrag1(&mut buffer))
@@ -160,20 +160,3 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     Some(defrag2(&files, frees))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
-}
@@ -3,7 +3,7 @@ use advent_of_code::directions::CARDINAL_DIRECTIONS;
 use advent_of_code::vec2::Vec2;
 use hashbrown::HashSet;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2024, 10);
 
 type Point = Vec2<i32>;
 
@@ -47,20 +47,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
     Some(points.len() as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
-}
@@ -2,7 +2,7 @@ use advent_of_code::numeric::count_digits;
 use hashbrown::HashMap;
 use rayon::prelude::*;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2024, 11);
 
 type Stone = u64;
 
@@ -17,7 +17,7 @@ fn solve(input: &str, total_rounds: u8) -> u64 {
                 recurse(1, rounds - 1, cache)
             } else {
                 let digits = count_digits(stone);
-                if digits % 2 == 0 {
+                if digits.is_multiple_of(2) {
                     let div = Stone::pow(10, digits / 2);
                     recurse(stone / div, rounds - 1, cache)
                         + recurse(stone % div, rounds - 1, cache)
@@ -44,20 +44,3 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     Some(solve(input, 75))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482));
-    }
-}
@@ -1,4 +1,3 @@
-use advent_of_code::char_grid::ByteGrid;
 use advent_of_code::directions::CARDINAL_DIRECTIONS;
 use advent_of_code::grid::Grid;
 use advent_of_code::vec2::Vec2;
@@ -6,7 +5,7 @@ use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 use rayon::prelude::*;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2024, 12);
 
 type Point = Vec2<i32>;
 
@@ -68,9 +67,9 @@ impl Region {
 }
 
 /// Converts character grid to int-grid so that each region gets its own numeric non-zero id.
-fn normalize_regions(grid: &ByteGrid) -> Grid<u32> {
+fn normalize_regions(grid: &Grid<u8>) -> Grid<u32> {
     let uninitialized = 0;
-    let mut result = Grid::new(grid.get_width(), grid.get_height(), uninitialized);
+    let mut result = grid.map(|_| uninitialized);
 
     let cardinal_neighbors = CARDINAL_DIRECTIONS.map(|d| d.to_vec());
     let mut id = 1;
@@ -89,7 +88,7 @@ fn normalize_regions(grid: &ByteGrid) -> Grid<u32> {
 }
 
 fn extract_regions(input: &str) -> impl Iterator<Item = Region> {
-    let grid = normalize_regions(&ByteGrid::new(input));
+    let grid = normalize_regions(&Grid::parse(input, |c| c));
 
     let mut groups = HashMap::<u32, Region>::new();
     for p in grid.points() {
@@ -117,49 +116,3 @@ pub fn part_two(input: &str) -> Option<u32> {
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use advent_of_code::template::read_file_part;
-
-    #[test]
-    fn test_part_one_1() {
-        assert_eq!(part_one(&read_file_part("examples", DAY, 1)), Some(140));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        assert_eq!(part_one(&read_file_part("examples", DAY, 2)), Some(772));
-    }
-
-    #[test]
-    fn test_part_one_3() {
-        assert_eq!(part_one(&read_file_part("examples", DAY, 3)), Some(1930));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        assert_eq!(part_two(&read_file_part("examples", DAY, 1)), Some(80));
-    }
-
-    #[test]
-    fn test_part_two_2() {
-        assert_eq!(part_two(&read_file_part("examples", DAY, 2)), Some(436));
-    }
-
-    #[test]
-    fn test_part_two_3() {
-        assert_eq!(part_two(&read_file_part("examples", DAY, 3)), Some(1206));
-    }
-
-    #[test]
-    fn test_part_two_4() {
-        assert_eq!(part_two(&read_file_part("examples", DAY, 4)), Some(236));
-    }
-
-    #[test]
-    fn test_part_two_5() {
-        assert_eq!(part_two(&read_file_part("examples", DAY, 5)), Some(368));
-    }
-}
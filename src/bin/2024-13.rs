@@ -3,7 +3,7 @@ use itertools::Itertools;
 
 type Vec2 = advent_of_code::vec2::Vec2<i64>;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2024, 13);
 
 fn tokens(a: Vec2, b: Vec2, c: Vec2) -> i64 {
     // Cramer's rule
@@ -45,20 +45,3 @@ pub fn part_one(input: &str) -> Option<i64> {
 pub fn part_two(input: &str) -> Option<i64> {
     Some(solve(input, Vec2::new(10000000000000, 10000000000000)))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875318608908));
-    }
-}
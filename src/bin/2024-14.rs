@@ -1,6 +1,15 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2024, 14, params: Params);
+
+advent_of_code::parameters! {
+    pub struct Params {
+        /// Width of the space the robots move in.
+        w: i32 = 101,
+        /// Height of the space the robots move in.
+        h: i32 = 103,
+    }
+}
 
 type Vec2 = advent_of_code::vec2::Vec2<i32>;
 
@@ -94,22 +103,18 @@ impl RobotSpace {
     }
 }
 
-fn solve1(input: &str, w: i32, h: i32) -> u32 {
-    let mut space = RobotSpace::parse(input, w, h);
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    let mut space = RobotSpace::parse(input, params.w, params.h);
 
     for _ in 0..100 {
         space.tick();
     }
 
-    space.checksum()
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(solve1(input, 101, 103))
+    Some(space.checksum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut space = RobotSpace::parse(input, 101, 103);
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
+    let mut space = RobotSpace::parse(input, params.w, params.h);
 
     for seconds in 0..u32::MAX {
         if space.has_tree() {
@@ -120,14 +125,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     None
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        assert_eq!(solve1(&input, 11, 7), 12);
-    }
-}
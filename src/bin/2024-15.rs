@@ -0,0 +1,80 @@
+use advent_of_code::char_grid::ByteGrid;
+use advent_of_code::directions::CardinalDirection;
+use advent_of_code::directions::CardinalDirection::{East, North, South, West};
+use advent_of_code::vec2::Vec2;
+
+advent_of_code::solution!(2024, 15);
+
+type Point = Vec2<i32>;
+
+/// Moves the robot and the boxes it pushes, if nothing hits a wall.
+fn try_move(grid: &mut ByteGrid, robot: Point, d: CardinalDirection) -> bool {
+    let vertical = matches!(d, North | South);
+
+    // cells to move, sorted by distance to the robot.
+    let mut moved = vec![robot];
+    let mut i = 0;
+    while i < moved.len() {
+        let target = moved[i] + d.to_vec();
+        i += 1;
+
+        let partner = match grid[&target] {
+            b'#' => return false,
+            b'[' if vertical => Some(target + East.to_vec()),
+            b']' if vertical => Some(target + West.to_vec()),
+            b'O' | b'[' | b']' => None,
+            _ => continue,
+        };
+
+        for p in [Some(target), partner].into_iter().flatten() {
+            if !moved.contains(&p) {
+                moved.push(p);
+            }
+        }
+    }
+
+    // the farthest cells move first, so every cell moves into an empty one.
+    for p in moved.iter().rev() {
+        grid.swap(p, &(*p + d.to_vec()));
+    }
+
+    true
+}
+
+fn solve(input: &str) -> u32 {
+    let (p1, p2) = input.split_once("\n\n").unwrap();
+
+    let mut grid = ByteGrid::new(p1);
+    let moves = p2
+        .chars()
+        .filter(|&c| c != '\n')
+        .map(CardinalDirection::from_code);
+    let mut robot = grid.find(b'@').expect("no robot");
+
+    for mv in moves {
+        if try_move(&mut grid, robot, mv) {
+            robot += mv.to_vec();
+        }
+    }
+
+    grid.points()
+        .filter(|p| matches!(grid[p], b'O' | b'['))
+        .map(|p| 100 * p.y + p.x)
+        .sum::<i32>() as u32
+}
+
+fn widen(input: &str) -> String {
+    input
+        .replace("#", "##")
+        .replace("O", "[]")
+        .replace(".", "..")
+        .replace("@", "@.")
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(solve(input))
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(solve(&widen(input)))
+}
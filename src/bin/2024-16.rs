@@ -5,7 +5,7 @@ use advent_of_code::shortest_path::{nodes_on_all_shortest_paths, shortest_path_l
 use advent_of_code::vec2::Vec2;
 use itertools::Itertools;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2024, 16);
 
 type Point = Vec2<i32>;
 
@@ -16,7 +16,7 @@ struct Maze<'a> {
 }
 
 impl Maze<'_> {
-    fn parse(input: &str) -> Maze {
+    fn parse(input: &str) -> Maze<'_> {
         let grid = ByteGrid::new(input);
         let start = grid.find(b'S').expect("no start");
         let end = grid.find(b'E').expect("no end");
@@ -89,32 +89,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     let points = states.iter().map(|s| s.p).unique().count();
     Some(points as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(7036));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(11048));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(64));
-    }
-}
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(2024, 17);
 
 #[derive(Debug)]
 struct VM<'a> {
@@ -13,7 +13,7 @@ struct VM<'a> {
 }
 
 impl VM<'_> {
-    fn new(program: &[u32], a: u64, b: u64, c: u64) -> VM {
+    fn new(program: &[u32], a: u64, b: u64, c: u64) -> VM<'_> {
         VM {
             a,
             b,
@@ -110,29 +110,3 @@ pub fn part_two(input: &str) -> Option<u64> {
             .unwrap()
     }))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::template::read_file_part("examples", DAY, 1);
-        let result = part_one(&input);
-        assert_eq!(result, Some(String::from("4,6,3,5,6,3,5,2,1,0")));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let input = advent_of_code::template::read_file_part("examples", DAY, 1);
-        let result = part_two(&input);
-        assert_eq!(result, Some(29328));
-    }
-
-    #[test]
-    fn test_part_two_2() {
-        let input = advent_of_code::template::read_file_part("examples", DAY, 2);
-        let result = part_two(&input);
-        assert_eq!(result, Some(117440));
-    }
-}
@@ -5,7 +5,16 @@ use hashbrown::HashSet;
 use itertools::Itertools;
 use std::collections::VecDeque;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(2024, 18, params: Params);
+
+advent_of_code::parameters! {
+    pub struct Params {
+        /// Largest coordinate of the memory space.
+        size: i32 = 70,
+        /// Number of fallen bytes in part one.
+        bytes: usize = 1024,
+    }
+}
 
 type Point = Vec2<i32>;
 
@@ -45,45 +54,20 @@ fn parse(input: &str) -> impl Iterator<Item = Point> + '_ {
     input.lines().map(|s| s.parse().unwrap())
 }
 
-fn solve1(input: &str, size: i32, take: usize) -> u32 {
-    let corrupted = parse(input).take(take).collect::<HashSet<_>>();
-    path_length(&corrupted, size).expect("no path")
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    let corrupted = parse(input).take(params.bytes).collect::<HashSet<_>>();
+    Some(path_length(&corrupted, params.size).expect("no path"))
 }
 
-fn solve2(input: &str, size: i32) -> String {
+pub fn part_two(input: &str, params: &Params) -> Option<String> {
     let points = parse(input).collect_vec();
 
     let i = binary_search(0..points.len(), |&i| {
         let corrupted_points = points.iter().take(i).cloned().collect::<HashSet<_>>();
-        path_length(&corrupted_points, size).is_none()
+        path_length(&corrupted_points, params.size).is_none()
     })
     .unwrap();
 
     let p = points[i - 1];
-    format!("{},{}", p.x, p.y)
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(solve1(input, 70, 1024))
-}
-
-pub fn part_two(input: &str) -> Option<String> {
-    Some(solve2(input, 70))
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        assert_eq!(solve1(&input, 6, 12), 22);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        assert_eq!(solve2(&input, 6), "6,1");
-    }
+    Some(format!("{},{}", p.x, p.y))
 }
@@ -1,7 +1,7 @@
 use hashbrown::HashSet;
 use rayon::prelude::*;
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(2024, 19);
 
 struct PatternData<'a> {
     patterns: HashSet<&'a str>,
@@ -46,7 +46,7 @@ impl PatternData<'_> {
     }
 }
 
-fn parse(input: &str) -> (PatternData, impl ParallelIterator<Item = &str>) {
+fn parse(input: &str) -> (PatternData<'_>, impl ParallelIterator<Item = &str>) {
     let (patterns_str, designs) = input.split_once("\n\n").unwrap();
 
     let patterns: HashSet<_> = patterns_str.split(", ").collect();
@@ -69,20 +69,3 @@ pub fn part_two(input: &str) -> Option<u64> {
     let (data, designs) = parse(input);
     Some(designs.map(|d| data.ways_to_make(d)).sum())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(16));
-    }
-}
@@ -3,7 +3,14 @@ use advent_of_code::vec2::Vec2;
 use num::abs;
 use rayon::prelude::*;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2024, 20, params: Params);
+
+advent_of_code::parameters! {
+    pub struct Params {
+        /// Picoseconds a cheat must save to be counted.
+        threshold: u16 = 100,
+    }
+}
 
 fn count_over_threshold(input: &str, distance: i32, threshold: u16) -> u32 {
     let track = ByteGrid::new(input);
@@ -40,29 +47,10 @@ fn count_over_threshold(input: &str, distance: i32, threshold: u16) -> u32 {
         .sum()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(count_over_threshold(input, 2, 100))
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(count_over_threshold(input, 20, 100))
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    Some(count_over_threshold(input, 2, params.threshold))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        assert_eq!(count_over_threshold(&input, 2, 2), 44);
-        assert_eq!(count_over_threshold(&input, 2, 10), 10);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        assert_eq!(count_over_threshold(&input, 20, 2), 3081);
-        assert_eq!(count_over_threshold(&input, 20, 10), 2268);
-    }
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
+    Some(count_over_threshold(input, 20, params.threshold))
 }
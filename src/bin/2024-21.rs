@@ -5,7 +5,7 @@ use std::str::FromStr;
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumString};
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(2024, 21);
 
 #[rustfmt::skip]
 #[derive(Copy, Clone, Eq, PartialEq, Hash, EnumString)]
@@ -196,14 +196,3 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     Some(solve(input, 25))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(126384));
-    }
-}
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use rayon::prelude::*;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2024, 22);
 
 fn next_secret(x: u32) -> u32 {
     let m = 16777216;
@@ -101,20 +101,4 @@ mod tests {
             ]
         );
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(37327623));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(23));
-    }
 }
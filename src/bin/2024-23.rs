@@ -2,14 +2,14 @@ use advent_of_code::collections::{extend, intersection};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(2024, 23);
 
 struct Graph<'a> {
     neighbors: HashMap<&'a str, HashSet<&'a str>>,
 }
 
 impl<'a> Graph<'a> {
-    fn parse(input: &str) -> Graph {
+    fn parse(input: &str) -> Graph<'_> {
         let mut neighbors = HashMap::<&str, HashSet<&str>>::new();
         for line in input.lines() {
             let (a, b) = line.split_once('-').unwrap();
@@ -101,20 +101,3 @@ pub fn part_two(input: &str) -> Option<String> {
             .join(","),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(String::from("co,de,ka,ta")));
-    }
-}
@@ -1,7 +1,7 @@
 use hashbrown::HashMap;
 use itertools::Itertools;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(2024, 24);
 
 struct Gate<'a> {
     lhs: &'a str,
@@ -100,7 +100,7 @@ fn find_bad_wires<'a, 'b: 'a>(gates: &'b HashMap<&'a str, Gate>) -> Vec<&'a str>
     bad
 }
 
-fn parse(input: &str) -> (HashMap<&str, Gate>, HashMap<&str, bool>) {
+fn parse(input: &str) -> (HashMap<&str, Gate<'_>>, HashMap<&str, bool>) {
     let (p1, p2) = input.split_once("\n\n").unwrap();
     let mut hardwires = HashMap::new();
     let mut gates = HashMap::new();
@@ -127,22 +127,3 @@ pub fn part_two(input: &str) -> Option<String> {
     let (circuit, _) = parse(input);
     Some(find_bad_wires(&circuit).iter().sorted().join(","))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one_1() {
-        let input = advent_of_code::template::read_file_part("examples", DAY, 1);
-        let result = part_one(&input);
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let input = advent_of_code::template::read_file_part("examples", DAY, 2);
-        let result = part_one(&input);
-        assert_eq!(result, Some(2024));
-    }
-}
@@ -1,7 +1,7 @@
 use advent_of_code::collections::transpose;
 use itertools::Itertools;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2024, 25);
 
 fn parse(input: &str) -> (Vec<Vec<u32>>, Vec<Vec<u32>>) {
     let mut locks = Vec::new();
@@ -44,14 +44,3 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
-    }
-}
@@ -0,0 +1,49 @@
+//! Runs every registered solution in a single process.
+//! Invoked by `cargo all` and `cargo time`, see `template::run_multi`.
+use std::process;
+
+use advent_of_code::template::{all_days, cli::AllArgs, registry, DaySelection, OutputFormat};
+use tinyjson::JsonValue;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: advent_of_code::template::alloc_counter::CountingAlloc =
+    advent_of_code::template::alloc_counter::CountingAlloc;
+
+fn main() {
+    let args = AllArgs::from_env();
+
+    let days = args
+        .days
+        .map_or_else(|| all_days().collect(), DaySelection::into_days);
+    let results = registry::run_solutions(
+        SOLUTIONS,
+        args.year,
+        &days,
+        args.time,
+        args.jobs,
+        args.format,
+    );
+
+    // when run by `cargo all` or `cargo time`, the results are printed by the parent.
+    if args.format == OutputFormat::Json && args.results.is_none() {
+        println!("{}", JsonValue::from(&results).stringify().unwrap());
+    }
+
+    if let Some(path) = args.results {
+        if let Err(e) = results.store_file(&path) {
+            eprintln!("Failed to store results: {e}");
+            process::exit(1);
+        }
+    }
+
+    if results.has_regressions() {
+        process::exit(1);
+    }
+}
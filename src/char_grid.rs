@@ -2,12 +2,16 @@ use crate::directions::CARDINAL_DIRECTIONS;
 use crate::grid::Grid;
 use crate::vec2::Vec2;
 use rayon::prelude::*;
+use std::borrow::Cow;
 use std::collections::VecDeque;
-use std::ops::{Index, Range};
+use std::ops::{Index, IndexMut, Range};
 type Coordinate = Vec2<i32>;
 
+/// A grid of bytes in the layout of the input, i.e. with a newline after every row.
+/// Parsing borrows the input, it's only copied on the first write.
+#[derive(Clone)]
 pub struct ByteGrid<'a> {
-    data: &'a [u8],
+    data: Cow<'a, [u8]>,
     width: usize,
     height: usize,
 }
@@ -22,12 +26,21 @@ impl<'a> ByteGrid<'a> {
         let height = input.lines().count();
 
         Self {
-            data: input.as_bytes(),
+            data: Cow::Borrowed(input.as_bytes()),
             width,
             height,
         }
     }
 
+    /// Copies the input, so the grid no longer borrows it.
+    pub fn into_owned(self) -> ByteGrid<'static> {
+        ByteGrid {
+            data: Cow::Owned(self.data.into_owned()),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
     pub fn get_width(&self) -> usize {
         self.width
     }
@@ -86,6 +99,27 @@ impl<'a> ByteGrid<'a> {
         p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
     }
 
+    pub fn set(&mut self, p: &Coordinate, c: u8) {
+        self[p] = c;
+    }
+
+    pub fn swap(&mut self, a: &Coordinate, b: &Coordinate) {
+        assert!(self.contains(a) && self.contains(b));
+        let (a, b) = (
+            self.index(a.x as usize, a.y as usize),
+            self.index(b.x as usize, b.y as usize),
+        );
+        self.data.to_mut().swap(a, b);
+    }
+
+    /// Sets every cell to `c`, keeping the newlines.
+    pub fn fill(&mut self, c: u8) {
+        let width = self.width;
+        for row in self.data.to_mut().chunks_mut(width + 1) {
+            row[..width].fill(c);
+        }
+    }
+
     fn index(&self, x: usize, y: usize) -> usize {
         // add 1 to line lengths for newlines
         y * (self.width + 1) + x
@@ -127,3 +161,42 @@ impl Index<&Coordinate> for ByteGrid<'_> {
         }
     }
 }
+
+impl IndexMut<&Coordinate> for ByteGrid<'_> {
+    fn index_mut(&mut self, index: &Coordinate) -> &mut Self::Output {
+        assert!(self.contains(index));
+        let offset = self.index(index.x as usize, index.y as usize);
+        &mut self.data.to_mut()[offset]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#.#\n..#\n";
+
+    #[test]
+    fn test_copy_on_write() {
+        let grid = ByteGrid::new(INPUT);
+        let mut copy = grid.clone();
+        copy.set(&Vec2::new(1, 0), b'@');
+        copy.swap(&Vec2::new(1, 0), &Vec2::new(0, 1));
+
+        assert!(matches!(grid.data, Cow::Borrowed(_)));
+        assert_eq!(grid[&Vec2::new(1, 0)], b'.');
+        assert_eq!(copy[&Vec2::new(1, 0)], b'.');
+        assert_eq!(copy[&Vec2::new(0, 1)], b'@');
+        assert_eq!(copy.find(b'@'), Some(Vec2::new(0, 1)));
+    }
+
+    #[test]
+    fn test_fill_keeps_layout() {
+        let mut grid = ByteGrid::new(INPUT).into_owned();
+        grid.fill(b'.');
+        grid[&Vec2::new(2, 1)] = b'#';
+
+        assert_eq!(grid.data.as_ref(), b"...\n..#\n");
+        assert_eq!(grid.find_all(b'.').count(), 5);
+    }
+}
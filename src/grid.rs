@@ -1,10 +1,12 @@
+use crate::char_grid::ByteGrid;
 use crate::vec2::Vec2;
 use itertools::iproduct;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::ops::{Index, IndexMut};
 
 type Coordinate = Vec2<i32>;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
@@ -19,9 +21,103 @@ impl<T: Clone> Grid<T> {
             height,
         }
     }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |p| {
+            self[&Vec2::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// Rotates by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        let h = self.height as i32;
+        Self::from_fn(self.height, self.width, |p| {
+            self[&Vec2::new(p.y, h - 1 - p.x)].clone()
+        })
+    }
+
+    /// Rotates by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let w = self.width as i32;
+        Self::from_fn(self.height, self.width, |p| {
+            self[&Vec2::new(w - 1 - p.y, p.x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width as i32;
+        Self::from_fn(self.width, self.height, |p| {
+            self[&Vec2::new(w - 1 - p.x, p.y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height as i32;
+        Self::from_fn(self.width, self.height, |p| {
+            self[&Vec2::new(p.x, h - 1 - p.y)].clone()
+        })
+    }
 }
 
 impl<T> Grid<T> {
+    /// Creates a grid by calling `f` for every point, row by row.
+    pub fn from_fn<F: FnMut(Coordinate) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let data = iproduct!(0..height as i32, 0..width as i32)
+            .map(|(y, x)| f(Vec2::new(x, y)))
+            .collect();
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a grid of equally long lines, mapping every byte to a cell.
+    pub fn parse<F: FnMut(u8) -> T>(input: &str, mut f: F) -> Self {
+        let width = input.lines().next().map_or(0, |line| line.len());
+        debug_assert!(
+            input.lines().all(|line| line.len() == width),
+            "All lines must have the same length"
+        );
+
+        let data: Vec<T> = input.lines().flat_map(str::bytes).map(&mut f).collect();
+        let height = data.len() / width.max(1);
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Renders the grid as lines of text, mapping every cell to a character.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
     pub fn contains(&self, p: &Coordinate) -> bool {
         p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
     }
@@ -35,7 +131,46 @@ impl<T> Grid<T> {
     }
 
     pub fn points(&self) -> impl Iterator<Item = Coordinate> + '_ {
-        iproduct!(0..self.height as i32, 0..self.width as i32).map(move |(x, y)| Vec2::new(x, y))
+        iproduct!(0..self.height as i32, 0..self.width as i32).map(move |(y, x)| Vec2::new(x, y))
+    }
+
+    /// Returns the first point, row by row, whose cell matches `predicate`.
+    pub fn position<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<Coordinate> {
+        self.points().find(|p| predicate(&self[p]))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[self.offset(0, y)..self.offset(0, y + 1)]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width);
+        (0..self.height).map(move |y| &self.data[self.offset(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Borrows the `width` x `height` area starting at `origin`, clipped to the grid.
+    pub fn view(&self, origin: Coordinate, width: usize, height: usize) -> GridView<'_, T> {
+        let clip = |start: i32, len: usize, max: usize| {
+            let start = start.clamp(0, max as i32) as usize;
+            (start, len.min(max - start))
+        };
+        let (x, width) = clip(origin.x, width, self.width);
+        let (y, height) = clip(origin.y, height, self.height);
+
+        GridView {
+            grid: self,
+            origin: Vec2::new(x as i32, y as i32),
+            width,
+            height,
+        }
     }
 
     fn offset(&self, x: usize, y: usize) -> usize {
@@ -89,3 +224,136 @@ impl<T> IndexMut<&Coordinate> for Grid<T> {
         &mut self.data[offset]
     }
 }
+
+/// Renders the cells of each row next to each other, rows are separated by newlines.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&ByteGrid<'_>> for Grid<u8> {
+    fn from(value: &ByteGrid<'_>) -> Self {
+        Self::from_fn(value.get_width(), value.get_height(), |p| value[&p])
+    }
+}
+
+/// A rectangular area of a [`Grid`], with coordinates relative to its origin.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Coordinate,
+    width: usize,
+    height: usize,
+}
+
+impl<T> GridView<'_, T> {
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: &Coordinate) -> bool {
+        p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
+    }
+
+    pub fn get(&self, p: &Coordinate) -> Option<&T> {
+        if self.contains(p) {
+            self.grid.get(&(self.origin + *p))
+        } else {
+            None
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        iproduct!(0..self.height as i32, 0..self.width as i32).map(move |(y, x)| Vec2::new(x, y))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        let x = self.origin.x as usize;
+        (0..self.height).map(move |y| &self.grid.row(self.origin.y as usize + y)[x..x + self.width])
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copies the area into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |p| self[&p].clone())
+    }
+}
+
+impl<T> Index<&Coordinate> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: &Coordinate) -> &Self::Output {
+        assert!(self.contains(index));
+        &self.grid[&(self.origin + *index)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn parse() -> Grid<char> {
+        Grid::parse(INPUT, |c| c as char)
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = parse();
+        assert_eq!(grid.get_width(), 3);
+        assert_eq!(grid.get_height(), 2);
+        assert_eq!(grid[&Vec2::new(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(grid.render(|c| c.to_ascii_uppercase()), "ABC\nDEF");
+        assert_eq!(Grid::from(&ByteGrid::new(INPUT)), grid.map(|&c| c as u8));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = parse();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.points().last(), Some(Vec2::new(2, 1)));
+        assert_eq!(grid.position(|&c| c == 'e'), Some(Vec2::new(1, 1)));
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = parse();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = parse();
+        let view = grid.view(Vec2::new(1, 0), 5, 2);
+        assert_eq!(view.get_width(), 2);
+        assert_eq!(view[&Vec2::new(1, 1)], 'f');
+        assert_eq!(view.get(&Vec2::new(2, 0)), None);
+        assert_eq!(view.rows().collect::<Vec<_>>(), [['b', 'c'], ['e', 'f']]);
+        assert_eq!(view.to_grid().to_string(), "bc\nef");
+    }
+}
@@ -1,144 +1,131 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
-
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
 use std::process;
 
-mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+use advent_of_code::template::cli::{Cli, Command};
+use advent_of_code::template::commands::time::{AllocOptions, CompareOptions};
+use advent_of_code::template::commands::{all, download, migrate, read, scaffold, solve, time};
+use advent_of_code::template::{all_days, config::Config, DaySelection, Puzzle, Year};
+use clap::Parser;
 
-    pub enum AppArguments {
-        Download {
-            day: Day,
-        },
-        Read {
-            day: Day,
-        },
-        Scaffold {
-            day: Day,
-            download: bool,
-            overwrite: bool,
-        },
-        Solve {
-            day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-        },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
-        },
-        #[cfg(feature = "today")]
-        Today,
-    }
+/// Resolves the year to operate on: `--year` takes precedence over the configured default.
+fn resolve_year(year: Option<Year>) -> Year {
+    year.or(Config::from_env().year).unwrap_or_else(|| {
+        eprintln!("Error: no year given, pass `--year` or set AOC_YEAR in `.cargo/config.toml`.");
+        process::exit(1);
+    })
+}
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+fn main() {
+    let Cli {
+        year,
+        format,
+        command,
+    } = Cli::parse();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+    if !matches!(command, Command::Migrate | Command::Completions { .. })
+        && migrate::has_legacy_data()
+    {
+        eprintln!(
+            "Warning: found data in the layout before it was split by year, \
+            run `cargo migrate` to move it to the folder of the year."
+        );
+    }
 
-                AppArguments::Time {
+    match command {
+        Command::All {
+            days,
+            release,
+            jobs,
+        } => {
+            let days = days.map_or_else(|| all_days().collect(), DaySelection::into_days);
+            all::handle(resolve_year(year), &days, release, jobs, format);
+        }
+        Command::Time {
+            days,
+            all,
+            store,
+            compare,
+            threshold,
+            fail_on_regression,
+            allocs,
+            dhat,
+            history,
+        } => {
+            let year = resolve_year(year);
+            let compare = compare.then(|| CompareOptions {
+                threshold: threshold.unwrap_or(Config::from_env().bench_threshold),
+                fail_on_regression,
+            });
+
+            match history {
+                Some(day) => time::history(year, day, format),
+                None => time::handle(
+                    year,
+                    days.map(DaySelection::into_days),
                     all,
-                    day: args.opt_free_from_str()?,
                     store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+                    compare,
+                    AllocOptions {
+                        count: allocs,
+                        profile_heap: dhat,
+                    },
+                    format,
+                ),
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
-
-        Ok(app_args)
-    }
-}
-
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+        Command::Download { day, force } => {
+            download::handle(Puzzle::new(resolve_year(year), day), force)
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        Command::Read { day, offline } => {
+            read::handle(Puzzle::new(resolve_year(year), day), offline)
+        }
+        Command::Scaffold {
+            day,
+            template,
+            download,
+            overwrite,
+        } => {
+            let puzzle = Puzzle::new(resolve_year(year), day);
+            // download first, the title and examples of the puzzle are rendered into the template.
+            if download {
+                download::handle(puzzle, false);
+                read::extract(puzzle);
             }
-        },
+            scaffold::handle(puzzle, template.as_deref(), overwrite);
+        }
+        Command::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            input,
+            params,
+        } => solve::handle(
+            Puzzle::new(resolve_year(year), day),
+            release,
+            dhat,
+            submit,
+            input,
+            params,
+            format,
+        ),
+        #[cfg(feature = "today")]
+        Command::Today => {
+            match Puzzle::today() {
+                Some(puzzle) => {
+                    download::handle(puzzle, false);
+                    read::handle(puzzle, false);
+                    scaffold::handle(puzzle, None, false)
+                }
+                None => {
+                    eprintln!(
+                        "`today` command can only be run between the 1st and \
+                        the 25th of december. Please use `scaffold` with a specific day."
+                    );
+                    process::exit(1)
+                }
+            };
+        }
+        Command::Migrate => migrate::handle(resolve_year(year)),
+        Command::Completions { shell } => Cli::print_completions(shell),
     };
 }
@@ -0,0 +1,125 @@
+//! A global allocator that counts allocations, installed by the solution binaries when built with the
+//! `count-allocs` feature. Unlike dhat it adds next to no overhead, so it can stay enabled while benching.
+//!
+//! Counts are process-wide: allocations of every thread running during a measurement are included,
+//! e.g. those of a part that timed out earlier and is still running in the background.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::template::runner::format_bytes;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Forwards to the system allocator, counting allocations and allocated bytes of all threads.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+fn record(bytes: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Whether the binary was built with the counting allocator. Without it, all counts stay zero.
+pub fn is_enabled() -> bool {
+    cfg!(all(feature = "count-allocs", not(feature = "dhat-heap")))
+}
+
+/// Allocations and allocated bytes, either since the process started or of a single run.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocCount {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl AllocCount {
+    /// Allocations since the process started.
+    pub fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Allocations since `start`, a previous [`AllocCount::now`].
+    pub fn since(start: Self) -> Self {
+        let now = Self::now();
+        Self {
+            allocations: now.allocations - start.allocations,
+            bytes: now.bytes - start.bytes,
+        }
+    }
+
+    /// Average over `runs` runs, rounded to the nearest integer.
+    pub fn per_run(self, runs: u64) -> Self {
+        let average = |total: u64| (total + runs / 2) / runs.max(1);
+        Self {
+            allocations: average(self.allocations),
+            bytes: average(self.bytes),
+        }
+    }
+}
+
+impl std::ops::AddAssign for AllocCount {
+    fn add_assign(&mut self, other: Self) {
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+    }
+}
+
+impl Display for AllocCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {}",
+            self.allocations,
+            format_bytes(self.bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AllocCount;
+
+    #[test]
+    fn averages_runs() {
+        let total = AllocCount {
+            allocations: 25,
+            bytes: 4096,
+        };
+        assert_eq!(
+            total.per_run(10),
+            AllocCount {
+                allocations: 3,
+                bytes: 410
+            }
+        );
+        assert_eq!(total.per_run(0), total);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
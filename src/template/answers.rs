@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+fn get_answers_path(year: Year) -> String {
+    year.data_path("answers.json")
+}
+
+/// Represents the accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers of a year, used to detect regressions.
+/// Can be deserialized from JSON, answers may be given as strings or integers:
+/// `{ "data": [{ "day": "01", "part_1": "1234", "part_2": null }] }`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Serialize answers as JSON and write them to the answers file of the given year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_answers_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of the given year. If not present, returns empty answers.
+    /// A file that can't be read or parsed is an error, checking against or storing over it would
+    /// silently drop the recorded answers.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_answers_path(year);
+        match fs::read_to_string(&path) {
+            Ok(content) => Answers::try_from(content).map_err(|e| format!("{path}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{path}: {e}")),
+        }
+    }
+
+    /// Returns the accepted answer of a part, if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records the accepted answer of a part. Returns whether the answer was previously unknown
+    /// or different.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) -> bool {
+        if self.get(day, part) == Some(answer) {
+            return false;
+        }
+
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.len() - 1
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.into()),
+            2 => entry.part_2 = Some(answer.into()),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Reads an optional answer given as a string or an integer.
+pub fn parse_answer(value: Option<&JsonValue>) -> Result<Option<String>, ()> {
+    match value {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+        Some(JsonValue::Number(n)) if n.fract() == 0.0 => Ok(Some(n.to_string())),
+        Some(_) => Err(()),
+    }
+}
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let answer = |answer: &Option<String>| match answer {
+            Some(answer) => JsonValue::String(answer.clone()),
+            None => JsonValue::Null,
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), answer(&value.part_1));
+        map.insert("part_2".into(), answer(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = parse_answer(json.get("part_1"))
+            .or(Err("Expected answer.part_1 to be null, string or integer."))?;
+
+        let part_2 = parse_answer(json.get("part_2"))
+            .or(Err("Expected answer.part_2 to be null, string or integer."))?;
+
+        Ok(Answer {
+            day,
+            part_1,
+            part_2,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part_1": "abc", "part_2": 1234 }, { "day": "02" }] }"#
+                .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("abc"));
+        assert_eq!(answers.get(day!(1), 2), Some("1234"));
+        assert_eq!(answers.get(day!(2), 1), None);
+        assert_eq!(answers.get(day!(3), 1), None);
+    }
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        assert!(answers.set(day!(3), 2, "48"));
+        assert!(!answers.set(day!(3), 2, "48"));
+        assert!(answers.set(day!(3), 1, "161"));
+        assert_eq!(answers.data.len(), 1);
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 1.5 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,344 @@
+/// Command-line model of the `advent_of_code` binary and the binaries it spawns.
+/// Child binaries are invoked with [`SolutionArgs::to_args`] and [`AllArgs::to_args`], and parse
+/// the same structs on their end.
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::template::{Day, DaySelection, OutputFormat, Year};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "advent_of_code",
+    version,
+    about = "Scaffold, solve and benchmark advent of code puzzles."
+)]
+pub struct Cli {
+    /// Year of advent to operate on [default: AOC_YEAR from `.cargo/config.toml`]
+    #[arg(long, global = true)]
+    pub year: Option<Year>,
+
+    /// Output format of `solve`, `all` and `time`
+    #[arg(long, global = true, default_value_t)]
+    pub format: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Download the input and description of a puzzle, unless they are cached
+    Download {
+        day: Day,
+        /// Download again even if the files are cached, overwriting local changes
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print the description of a puzzle, extracting its examples and answers
+    Read {
+        day: Day,
+        /// Render the cached description without downloading it again
+        #[arg(long)]
+        offline: bool,
+    },
+
+    /// Create the solution file of a puzzle from the template
+    Scaffold {
+        day: Day,
+        /// Named template from the templates directory, e.g. `grid` [default: built-in template]
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+        /// Also download the input and description
+        #[arg(long)]
+        download: bool,
+        /// Replace an existing solution file
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Run the solution of a single day
+    Solve {
+        day: Day,
+        /// Build with optimizations
+        #[arg(long)]
+        release: bool,
+        /// Profile heap allocations with dhat
+        #[arg(long, conflicts_with = "release")]
+        dhat: bool,
+        /// Submit the answer of a part
+        #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+        submit: Option<u8>,
+        /// Read the input from a file, or from stdin if `-` [default: the downloaded input]
+        #[arg(long, value_name = "PATH", conflicts_with = "submit")]
+        input: Option<String>,
+        /// Override a parameter of the solution, e.g. `size=6`
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param, conflicts_with = "submit")]
+        params: Vec<(String, String)>,
+    },
+
+    /// Run the solutions of several days
+    All {
+        /// Days to run, e.g. `1..10`, `3,7,12` or `!25` [default: all days]
+        days: Option<DaySelection>,
+        /// Build with optimizations
+        #[arg(long)]
+        release: bool,
+        /// Number of days to run concurrently, `0` uses all cores
+        #[arg(long, default_value_t = 1)]
+        jobs: usize,
+    },
+
+    /// Benchmark solutions
+    Time {
+        /// Days to bench, e.g. `1..10`, `3,7,12` or `!25` [default: days without stored timings]
+        days: Option<DaySelection>,
+        /// Bench all days, including those with stored timings
+        #[arg(long)]
+        all: bool,
+        /// Store timings and update the README
+        #[arg(long)]
+        store: bool,
+        /// Compare against stored timings
+        #[arg(long)]
+        compare: bool,
+        /// Slowdown in percent reported as a regression [default: AOC_BENCH_THRESHOLD or 10]
+        #[arg(long, requires = "compare")]
+        threshold: Option<f64>,
+        /// Exit with a non-zero status if any part regressed
+        #[arg(long, requires = "compare")]
+        fail_on_regression: bool,
+        /// Count the allocations of each benched run
+        #[arg(long)]
+        allocs: bool,
+        /// Also record heap allocations of each part with dhat, in a separate untimed run
+        #[arg(long)]
+        dhat: bool,
+        /// Print the recorded timings of a day instead of benching
+        #[arg(long, value_name = "DAY", conflicts_with_all = ["days", "all", "store", "compare", "allocs", "dhat"])]
+        history: Option<Day>,
+    },
+
+    /// Scaffold, download and read today's puzzle
+    #[cfg(feature = "today")]
+    Today,
+
+    /// Move data stored in the layout before it was split by year to the folder of the year
+    Migrate,
+
+    /// Print a completion script for a shell
+    Completions { shell: Shell },
+}
+
+impl Cli {
+    /// Print a completion script of this command-line to stdout.
+    pub fn print_completions(shell: Shell) {
+        let mut command = <Self as clap::CommandFactory>::command();
+        let name = command.get_name().to_string();
+        clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+    }
+}
+
+/// Parses a parameter override of the form `name=value`.
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(name, value)| (name.into(), value.into()))
+        .ok_or_else(|| format!("expected `NAME=VALUE`, got `{s}`"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Flags of a solution binary, passed by `solve`.
+#[derive(Debug, Default, PartialEq, Parser)]
+pub struct SolutionArgs {
+    /// Output format of the results
+    #[arg(long, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Bench every part
+    #[arg(long)]
+    pub time: bool,
+
+    /// Submit the answer of a part
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub submit: Option<u8>,
+
+    /// Read the input from a file, or from stdin if `-`
+    #[arg(long, value_name = "PATH", conflicts_with = "submit")]
+    pub input: Option<String>,
+
+    /// Override a parameter of the solution, e.g. `size=6`
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param, conflicts_with = "submit")]
+    pub params: Vec<(String, String)>,
+}
+
+impl SolutionArgs {
+    /// Parse the arguments of the current process, exits with usage on errors.
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+
+    /// The arguments that parse back into `self`.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--format".into(), self.format.to_string()];
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+
+        if let Some(input) = &self.input {
+            args.extend(["--input".into(), input.clone()]);
+        }
+
+        for (name, value) in &self.params {
+            args.extend(["--param".into(), format!("{name}={value}")]);
+        }
+
+        args
+    }
+}
+
+/// Flags of the `all` binary, passed by `all` and `time`.
+#[derive(Debug, PartialEq, Parser)]
+pub struct AllArgs {
+    #[arg(long)]
+    pub year: Year,
+
+    /// Days to run [default: all days]
+    #[arg(long)]
+    pub days: Option<DaySelection>,
+
+    /// File the results are written to as JSON
+    #[arg(long)]
+    pub results: Option<String>,
+
+    /// Output format of the results
+    #[arg(long, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Number of days to run concurrently, `0` uses all cores
+    #[arg(long, default_value_t = 1)]
+    pub jobs: usize,
+
+    /// Bench every part
+    #[arg(long)]
+    pub time: bool,
+}
+
+impl AllArgs {
+    /// Parse the arguments of the current process, exits with usage on errors.
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+
+    /// The arguments that parse back into `self`.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--year".into(), self.year.to_string()];
+
+        if let Some(days) = &self.days {
+            args.extend(["--days".into(), days.to_string()]);
+        }
+
+        if let Some(results) = &self.results {
+            args.extend(["--results".into(), results.clone()]);
+        }
+
+        args.extend(["--format".into(), self.format.to_string()]);
+        args.extend(["--jobs".into(), self.jobs.to_string()]);
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        args
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use clap::{CommandFactory, Parser};
+
+    use super::{AllArgs, Cli, Command, SolutionArgs};
+    use crate::template::{DaySelection, OutputFormat};
+    use crate::{day, year};
+
+    #[test]
+    fn verifies_cli() {
+        Cli::command().debug_assert();
+        SolutionArgs::command().debug_assert();
+        AllArgs::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_commands() {
+        let cli =
+            Cli::try_parse_from(["aoc", "solve", "8", "--submit", "2", "--year", "2023"]).unwrap();
+        assert_eq!(cli.year, Some(year!(2023)));
+        assert!(matches!(
+            cli.command,
+            Command::Solve {
+                submit: Some(2),
+                release: false,
+                ..
+            }
+        ));
+
+        let cli =
+            Cli::try_parse_from(["aoc", "--format", "json", "all", "1..3", "--jobs", "4"]).unwrap();
+        assert_eq!(cli.format, OutputFormat::Json);
+        assert!(matches!(cli.command, Command::All { jobs: 4, .. }));
+
+        let cli = Cli::try_parse_from(["aoc", "scaffold", "8", "--template", "grid"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Scaffold { template: Some(name), .. } if name == "grid"
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(Cli::try_parse_from(["aoc", "solve", "8", "--relase"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "solve", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "solve", "8", "--submit", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "time", "--threshold", "5"]).is_err());
+        assert!(
+            Cli::try_parse_from(["aoc", "solve", "8", "--submit", "1", "--input", "-"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["aoc", "frobnicate"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_child_arguments() {
+        let args = SolutionArgs {
+            format: OutputFormat::Json,
+            time: true,
+            submit: None,
+            input: Some("-".into()),
+            params: vec![("size".into(), "6".into()), ("bytes".into(), "12".into())],
+        };
+        let parsed = SolutionArgs::try_parse_from(
+            ["bin"].into_iter().map(String::from).chain(args.to_args()),
+        );
+        assert_eq!(parsed.unwrap(), args);
+
+        let args = AllArgs {
+            year: year!(2024),
+            days: Some(DaySelection::from_iter([day!(3), day!(12)])),
+            results: Some("/tmp/results.json".into()),
+            format: OutputFormat::Text,
+            jobs: 4,
+            time: false,
+        };
+        let parsed =
+            AllArgs::try_parse_from(["all"].into_iter().map(String::from).chain(args.to_args()));
+        assert_eq!(parsed.unwrap(), args);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
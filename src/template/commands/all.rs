@@ -1,5 +1,31 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{collections::HashSet, process};
+use tinyjson::JsonValue;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{run_multi, Build};
+use crate::template::{Day, OutputFormat, Year};
+
+pub fn handle(
+    year: Year,
+    days: &HashSet<Day>,
+    is_release: bool,
+    jobs: usize,
+    format: OutputFormat,
+) {
+    let build = if is_release {
+        Build::Release
+    } else {
+        Build::Debug
+    };
+    let results = run_multi(year, days, build, false, jobs, format).unwrap_or_else(|e| {
+        eprintln!("Failed to run solutions: {e:?}");
+        process::exit(1);
+    });
+
+    if format == OutputFormat::Json {
+        println!("{}", JsonValue::from(&results).stringify().unwrap());
+    }
+
+    if results.has_regressions() {
+        process::exit(1);
+    }
 }
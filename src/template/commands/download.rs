@@ -1,14 +1,27 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::provider::{FileCache, Resource};
+use crate::template::Puzzle;
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: Puzzle, force: bool) {
+    let cache = FileCache::from_env();
+
+    println!("---");
+    for resource in [Resource::Input, Resource::Description] {
+        let result = if force {
+            cache.refresh(puzzle, resource)
+        } else {
+            cache.get(puzzle, resource)
+        };
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+        if let Err(e) = result {
+            eprintln!("Failed to download {resource}: {e}");
+            process::exit(1);
+        }
+
+        let path = cache.path(puzzle, resource);
+        println!(
+            "🎄 Successfully wrote {resource} to \"{}\".",
+            path.display()
+        );
+    }
 }
@@ -0,0 +1,72 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::Year;
+
+/// Folders and files of the data layout before it was split by year, e.g. `data/inputs/01.txt`.
+const LEGACY_DATA: [&str; 4] = ["inputs", "examples", "puzzles", "timings.json"];
+
+/// Files stored in the layout before it was split by year, skipping placeholders like `.keep`.
+fn legacy_files() -> Vec<PathBuf> {
+    let mut files = vec![];
+
+    for name in LEGACY_DATA {
+        let legacy = Path::new("data").join(name);
+        if legacy.is_file() {
+            files.push(legacy);
+        } else if let Ok(entries) = fs::read_dir(&legacy) {
+            files.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+                    .map(|entry| entry.path()),
+            );
+        }
+    }
+
+    files.sort_unstable();
+    files
+}
+
+/// Whether data is stored in the layout before it was split by year.
+pub fn has_legacy_data() -> bool {
+    !legacy_files().is_empty()
+}
+
+/// Moves data stored in the layout before it was split by year to the data folder of `year`.
+/// Files that already exist there are left in place with a warning.
+pub fn handle(year: Year) {
+    let files = legacy_files();
+    if files.is_empty() {
+        println!("No data stored in the layout before it was split by year.");
+        return;
+    }
+
+    for from in files {
+        // e.g. `data/inputs/01.txt` to `data/2024/inputs/01.txt`.
+        let relative = from.strip_prefix("data").unwrap_or(&from);
+        let to = PathBuf::from(year.data_path(&relative.to_string_lossy()));
+        move_legacy_file(&from, &to);
+    }
+}
+
+fn move_legacy_file(from: &Path, to: &Path) {
+    let (from_str, to_str) = (from.display(), to.display());
+
+    if to.exists() {
+        eprintln!("Warning: not moving \"{from_str}\" to \"{to_str}\", which already exists.");
+        return;
+    }
+
+    let result = to
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::rename(from, to));
+
+    match result {
+        Ok(()) => println!("Moved \"{from_str}\" to \"{to_str}\"."),
+        Err(e) => eprintln!("Failed to move \"{from_str}\" to \"{to_str}\": {e}"),
+    }
+}
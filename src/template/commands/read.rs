@@ -1,15 +1,107 @@
-use std::process;
+use std::{fs, path::Path, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::answers::Answers;
+use crate::template::description::{self, PartDescription};
+use crate::template::examples::example_files;
+use crate::template::provider::{FileCache, ProviderError, Resource};
+use crate::template::Puzzle;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(puzzle: Puzzle, offline: bool) {
+    let cache = FileCache::from_env();
+
+    let description = if offline {
+        fs::read_to_string(cache.path(puzzle, Resource::Description)).map_err(ProviderError::from)
+    } else {
+        // the description is fetched again so part two shows up once part one is solved.
+        cache.refresh(puzzle, Resource::Description).or_else(|e| {
+            eprintln!("Showing the cached puzzle, failed to download it: {e}");
+            cache.get(puzzle, Resource::Description)
+        })
+    };
+
+    let description = match description {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("Failed to read puzzle: {e}");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", description::render(&description));
+    extract_from(puzzle, &description);
+}
+
+/// Writes the examples and records the answers of the cached description, without printing it.
+pub fn extract(puzzle: Puzzle) {
+    let path = FileCache::from_env().path(puzzle, Resource::Description);
+    match fs::read_to_string(&path) {
+        Ok(description) => extract_from(puzzle, &description),
+        Err(e) => eprintln!("Failed to read puzzle \"{}\": {e}", path.display()),
     }
+}
+
+fn extract_from(puzzle: Puzzle, description: &str) {
+    let parts = description::parse(description);
+    write_examples(puzzle, &parts);
+    record_answers(puzzle, &parts);
+}
+
+/// Writes the examples of the description, unless the puzzle already has non-empty example files.
+/// A second, different example of part two is written to `{day}-2.txt`.
+fn write_examples(puzzle: Puzzle, parts: &[PartDescription]) {
+    let has_examples = example_files(puzzle)
+        .iter()
+        .any(|path| fs::metadata(path).is_ok_and(|m| m.len() > 0));
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    if has_examples {
+        return;
+    }
+
+    let mut examples: Vec<&String> = parts.iter().filter_map(|p| p.example.as_ref()).collect();
+    examples.dedup();
+
+    let paths: Vec<String> = match examples.len() {
+        0 => return,
+        1 => vec![puzzle.data_path("examples", "txt")],
+        n => (1..=n as u8)
+            .map(|i| puzzle.data_part_path("examples", i, "txt"))
+            .collect(),
     };
+
+    println!("---");
+    for (path, example) in paths.iter().zip(examples) {
+        let written = Path::new(path)
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, example));
+        match written {
+            Ok(()) => println!("🎄 Extracted example to \"{path}\""),
+            Err(e) => eprintln!("Failed to write example \"{path}\": {e}"),
+        }
+    }
+}
+
+/// Records the answers given in the description as the accepted answers of the puzzle.
+fn record_answers(puzzle: Puzzle, parts: &[PartDescription]) {
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not recording answers: failed to read the accepted answers {e}");
+            return;
+        }
+    };
+    let mut changed = false;
+
+    for (part, answer) in (1..=2).zip(parts) {
+        if let Some(answer) = &answer.answer {
+            changed |= answers.set(puzzle.day, part, answer);
+        }
+    }
+
+    if changed {
+        match answers.store_file(puzzle.year) {
+            Ok(()) => println!("🎄 Recorded the answers of day {}", puzzle.day),
+            Err(e) => eprintln!("Failed to store answers: {e}"),
+        }
+    }
 }
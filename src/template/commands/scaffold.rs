@@ -1,14 +1,62 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{config::Config, examples::example_files, Puzzle};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Loads a named template from the templates directory, or the built-in template if `name` is `None`.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    let dir = Config::from_env().templates_dir;
+    fs::read_to_string(Path::new(&dir).join(format!("{name}.txt"))).map_err(|e| {
+        let mut available: Vec<String> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                file_name.strip_suffix(".txt").map(String::from)
+            })
+            .collect();
+        available.sort_unstable();
+
+        format!(
+            "could not read template `{name}` from \"{dir}\": {e}. Available templates: {}",
+            available.join(", ")
+        )
+    })
+}
+
+/// Title of a puzzle from its cached description, e.g. `Reindeer Maze`.
+fn puzzle_title(puzzle: Puzzle) -> Option<String> {
+    let description = fs::read_to_string(puzzle.data_path("puzzles", "md")).ok()?;
+    let heading = description.lines().find(|line| line.starts_with("## "))?;
+
+    let title = heading.trim_start_matches("## ").trim_matches(['-', ' ']);
+    let title = title.split_once(": ").map_or(title, |(_, title)| title);
+    Some(title.into())
+}
+
+/// Replaces the placeholders of a template:
+/// `%YEAR%`, `%DAY%` (`08`), `%DAY_NUMBER%` (`8`), `%TITLE%` and `%EXAMPLE_COUNT%`.
+pub fn render(template: &str, puzzle: Puzzle, title: &str, examples: usize) -> String {
+    template
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%TITLE%", title)
+        .replace("%EXAMPLE_COUNT%", &examples.to_string())
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -19,18 +67,41 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file unless it exists already, e.g. because the puzzle was downloaded first.
+/// Returns whether the file was created.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, template: Option<&str>, overwrite: bool) {
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let title = puzzle_title(puzzle).unwrap_or_else(|| "Untitled".into());
+    let example_count = example_files(puzzle).len();
+    let contents = render(
+        &template,
+        puzzle,
+        &title,
+        // at least one, as scaffolding creates an example file if there is none.
+        example_count.max(1),
+    );
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +111,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -55,25 +122,54 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    // examples extracted from the downloaded puzzle may be split per part, e.g. `08-1.txt`.
+    if example_count == 0 {
+        match create_file(&example_path) {
+            Ok(true) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::Puzzle;
+    use crate::{day, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let puzzle = Puzzle::new(year!(2024), day!(8));
+        let template = "solution!(%YEAR%, %DAY_NUMBER%); // %DAY%: %TITLE% (%EXAMPLE_COUNT%)";
+        assert_eq!(
+            render(template, puzzle, "Resonant Collinearity", 2),
+            "solution!(2024, 8); // 08: Resonant Collinearity (2)"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
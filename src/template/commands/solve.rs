@@ -1,9 +1,18 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::child_commands;
+use crate::template::{cli::SolutionArgs, OutputFormat, Puzzle};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<String>,
+    params: Vec<(String, String)>,
+    format: OutputFormat,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -18,17 +27,25 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    let child_args = SolutionArgs {
+        format,
+        time: false,
+        submit: submit_part,
+        input,
+        params,
+    };
+    cmd_args.extend(child_args.to_args());
 
-    let mut cmd = Command::new("cargo")
+    let mut cmd = child_commands::cargo()
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // mirror the exit status, solution binaries fail on answer regressions.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
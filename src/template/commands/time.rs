@@ -1,40 +1,280 @@
-use std::collections::HashSet;
-
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
+use std::collections::{HashMap, HashSet};
+use std::process;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::environment::Environment;
+use crate::template::history::{format_timestamp, History};
+use crate::template::run_multi::{child_commands, run_multi, Build};
+use crate::template::timings::{PartDelta, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, OutputFormat, Year};
+
+/// Options of `time --compare`.
+pub struct CompareOptions {
+    /// Slowdown in percent above which a part is flagged as a regression.
+    pub threshold: f64,
+    /// Exit with a non-zero status if any part regressed.
+    pub fail_on_regression: bool,
+}
+
+/// Options of `time --allocs` and `time --dhat`.
+pub struct AllocOptions {
+    /// Count the allocations of each benched run.
+    pub count: bool,
+    /// Record heap allocations of each part with dhat, in a separate untimed run.
+    pub profile_heap: bool,
+}
+
+pub fn handle(
+    year: Year,
+    days: Option<HashSet<Day>>,
+    run_all: bool,
+    store: bool,
+    compare: Option<CompareOptions>,
+    allocs: AllocOptions,
+    format: OutputFormat,
+) {
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read stored timings {e}");
+        process::exit(1);
+    });
+
+    let days_to_run = days.unwrap_or_else(|| {
+        // comparing against stored timings needs the stored days to be run again.
+        if run_all || compare.is_some() {
+            all_days().collect()
+        } else {
+            // when the `--all` flag is not set, filter out days that are fully benched.
+            all_days()
+                .filter(|day| !stored_timings.is_day_complete(*day))
+                .collect()
+        }
+    });
+
+    let build = if allocs.count {
+        Build::CountAllocs
+    } else {
+        Build::Release
+    };
+    let results = run_multi(year, &days_to_run, build, true, 1, format).unwrap_or_else(|e| {
+        eprintln!("Failed to run solutions: {e:?}");
+        process::exit(1);
+    });
+    let mut timings = Timings::from(&results).with_environment(&Environment::current(build));
+
+    // the dhat allocator distorts timings, allocations are recorded in a separate untimed run.
+    // its output would repeat the timed run's, only its results are used.
+    if allocs.profile_heap {
+        match child_commands::run_solutions(
+            year,
+            &days_to_run,
+            false,
+            Build::Dhat,
+            1,
+            OutputFormat::Json,
+            true,
+        ) {
+            Ok(profiled) => timings = timings.with_heap(&Timings::from(&profiled)),
+            Err(e) => {
+                eprintln!("Failed to profile heap allocations: {e:?}");
+                process::exit(1);
             }
-        },
-        |day| HashSet::from([day]),
-    );
+        }
+    }
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let comparison = compare.as_ref().map(|options| {
+        let differences = stored_timings.environment_differences(&timings);
+        print_environment_warning(&differences);
+
+        let deltas = stored_timings.compare(&timings);
+        if format.is_text() {
+            print_comparison(&deltas, options.threshold);
+        }
+        (options.threshold, deltas, differences)
+    });
+
+    let has_regressions = comparison
+        .as_ref()
+        .is_some_and(|(threshold, deltas, _)| deltas.iter().any(|d| d.is_regression(*threshold)));
+
+    // stdout is a single JSON document in machine readable mode: the results and the comparison.
+    if format == OutputFormat::Json {
+        let mut json = JsonValue::from(&results);
+        if let (JsonValue::Object(map), Some((threshold, deltas, differences))) =
+            (&mut json, &comparison)
+        {
+            map.insert(
+                "comparison".into(),
+                comparison_json(deltas, differences, *threshold),
+            );
+        }
+        println!("{}", json.stringify().unwrap());
+    }
 
     if store {
+        // read before storing anything, a history that can't be parsed must not be overwritten.
+        let mut history = read_history(year);
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
+        history.record(&timings);
+        history.store_file(year).unwrap();
+
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                if format.is_text() {
+                    println!();
+                    println!("Stored updated benchmarks.");
+                }
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if has_regressions && compare.is_some_and(|options| options.fail_on_regression) {
+        process::exit(1);
+    }
+}
+
+fn read_history(year: Year) -> History {
+    History::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read timing history {e}");
+        process::exit(1);
+    })
+}
+
+/// Timings benched on a different machine or build aren't comparable, warn about it on stderr.
+fn print_environment_warning(differences: &[String]) {
+    if differences.is_empty() {
+        return;
+    }
+
+    eprintln!("Warning: stored timings were benched in a different environment:");
+    for difference in differences {
+        eprintln!("  {difference}");
+    }
+}
+
+fn print_comparison(deltas: &[PartDelta], threshold: f64) {
+    println!();
+    println!("Comparison to stored timings:");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    for delta in deltas {
+        let verdict = if delta.is_regression(threshold) {
+            " ✘ regression"
+        } else {
+            ""
+        };
+        println!(
+            "Day {} part {}: {:.1?} -> {:.1?} ({:+.1}%){verdict}",
+            delta.day,
+            delta.part,
+            delta.before,
+            delta.after,
+            delta.change_percent()
+        );
+    }
+
+    let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+    println!("{regressions} regression(s) above {threshold}%.");
+}
+
+/// The comparison to stored timings, e.g.
+/// `{ "threshold": 10, "regressions": 1, "environment_differences": [], "deltas": [{ "day": "01", "part": 1,
+/// "before_nanos": 1000, "after_nanos": 1200, "change_percent": 20, "regression": true }] }`
+fn comparison_json(deltas: &[PartDelta], differences: &[String], threshold: f64) -> JsonValue {
+    let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+    let deltas: Vec<JsonValue> = deltas
+        .iter()
+        .map(|delta| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(delta.day.to_string()));
+            map.insert("part".into(), JsonValue::Number(f64::from(delta.part)));
+            map.insert(
+                "before_nanos".into(),
+                JsonValue::Number(delta.before.as_nanos() as f64),
+            );
+            map.insert(
+                "after_nanos".into(),
+                JsonValue::Number(delta.after.as_nanos() as f64),
+            );
+            map.insert(
+                "change_percent".into(),
+                JsonValue::Number(delta.change_percent()),
+            );
+            map.insert(
+                "regression".into(),
+                JsonValue::Boolean(delta.is_regression(threshold)),
+            );
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("threshold".into(), JsonValue::Number(threshold));
+    map.insert("regressions".into(), JsonValue::Number(regressions as f64));
+    map.insert(
+        "environment_differences".into(),
+        JsonValue::Array(
+            differences
+                .iter()
+                .map(|d| JsonValue::String(d.clone()))
+                .collect(),
+        ),
+    );
+    map.insert("deltas".into(), JsonValue::Array(deltas));
+    JsonValue::Object(map)
+}
+
+/// Print how the runtime of a day changed across recorded runs.
+pub fn history(year: Year, day: Day, format: OutputFormat) {
+    let history = read_history(year);
+    let entries = History {
+        data: history.for_day(day).cloned().collect(),
+    };
+
+    if !format.is_text() {
+        println!("{}", JsonValue::from(&entries).stringify().unwrap());
+        return;
+    }
+
+    if entries.data.is_empty() {
+        println!("No recorded timings for day {day}, run `cargo time {day} --store` first.");
+        return;
+    }
+
+    println!("Day {day} history:");
+
+    let mut previous: Option<f64> = None;
+    for entry in &entries.data {
+        let timing = &entry.timing;
+        let change = previous
+            .map(|before| {
+                format!(
+                    " ({:+.1}%)",
+                    (timing.total_nanos - before) / before.max(1.0) * 100.0
+                )
+            })
+            .unwrap_or_default();
+
+        println!(
+            "{} {:<16} part 1: {:<10} part 2: {:<10} total: {:.1?}{change}",
+            format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            timing.part_1.map_or("-".into(), |t| t.to_string()),
+            timing.part_2.map_or("-".into(), |t| t.to_string()),
+            Duration::from_nanos(timing.total_nanos as u64),
+        );
+
+        previous = Some(timing.total_nanos);
+    }
 }
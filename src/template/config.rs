@@ -0,0 +1,179 @@
+/// Project-wide settings that apply to every command.
+/// Values are read from the environment, which `.cargo/config.toml` populates for all cargo aliases.
+use std::{env, time::Duration};
+
+use crate::template::Year;
+
+/// Environment variable holding the default year of advent.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+/// Environment variable holding the time budget of a benchmark, in milliseconds.
+pub const BENCH_BUDGET_ENV: &str = "AOC_BENCH_BUDGET_MS";
+/// Environment variable holding the number of warm-up iterations before a benchmark.
+pub const BENCH_WARMUP_ENV: &str = "AOC_BENCH_WARMUP";
+/// Environment variable holding the slowdown, in percent, that `time --compare` flags as a regression.
+pub const BENCH_THRESHOLD_ENV: &str = "AOC_BENCH_THRESHOLD";
+/// Environment variable holding the time a part may run before it's reported as timed out, in milliseconds.
+/// A value of `0` disables the timeout.
+pub const PART_TIMEOUT_ENV: &str = "AOC_PART_TIMEOUT_MS";
+/// Environment variable holding the directory `scaffold --template` loads named templates from.
+pub const TEMPLATES_DIR_ENV: &str = "AOC_TEMPLATES_DIR";
+/// Environment variable holding the sections of the README benchmark report, see [`ReportOptions`].
+pub const README_REPORT_ENV: &str = "AOC_README_REPORT";
+/// Environment variable holding contact info, e.g. an email address, sent along with downloads.
+pub const CONTACT_ENV: &str = "AOC_CONTACT";
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The year commands operate on when none is passed via `--year`.
+    pub year: Option<Year>,
+    /// Approximate time spent benching a single part.
+    pub bench_budget: Duration,
+    /// Iterations executed before a part is benched, to warm up caches and branch predictors.
+    pub bench_warmup: u32,
+    /// Slowdown in percent above which `time --compare` reports a regression.
+    pub bench_threshold: f64,
+    /// Time a part may run before it's given up on, `None` waits indefinitely.
+    pub part_timeout: Option<Duration>,
+    /// Directory holding the named scaffold templates, e.g. `templates/grid.txt`.
+    pub templates_dir: String,
+    /// Sections of the benchmark report `time --store` writes to the README.
+    pub readme_report: ReportOptions,
+    /// Contact info included in the user agent of downloads, as advent of code asks of automated tools.
+    pub contact: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            bench_budget: Duration::from_secs(1),
+            bench_warmup: 10,
+            bench_threshold: 10.0,
+            part_timeout: Some(Duration::from_secs(60)),
+            templates_dir: "templates".into(),
+            readme_report: ReportOptions::default(),
+            contact: None,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the configuration from the environment. Invalid values are treated as unset.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        Self {
+            year: env_value(YEAR_ENV).or(default.year),
+            bench_budget: env_value(BENCH_BUDGET_ENV)
+                .map(Duration::from_millis)
+                .unwrap_or(default.bench_budget),
+            bench_warmup: env_value(BENCH_WARMUP_ENV).unwrap_or(default.bench_warmup),
+            bench_threshold: env_value(BENCH_THRESHOLD_ENV).unwrap_or(default.bench_threshold),
+            part_timeout: env_value(PART_TIMEOUT_ENV)
+                .map(|millis| (millis > 0).then(|| Duration::from_millis(millis)))
+                .unwrap_or(default.part_timeout),
+            templates_dir: env_value(TEMPLATES_DIR_ENV).unwrap_or(default.templates_dir),
+            readme_report: env_value(README_REPORT_ENV).unwrap_or(default.readme_report),
+            contact: env_value::<String>(CONTACT_ENV)
+                .filter(|contact| !contact.trim().is_empty())
+                .or(default.contact),
+        }
+    }
+}
+
+/// Optional sections of the README benchmark report, a comma-separated list of
+/// `bars`, `samples`, `links`, `ranking[=N]` and `chart`, e.g. `bars,ranking=3`.
+/// An empty list writes the plain table of part timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportOptions {
+    /// A bar per day showing its cost relative to the slowest day.
+    pub bars: bool,
+    /// The number of benchmark samples of each part.
+    pub samples: bool,
+    /// Links to the puzzle pages.
+    pub links: bool,
+    /// Number of days in the slowest-days ranking, `0` omits the ranking.
+    pub ranking: usize,
+    /// An SVG chart of the timings, written next to the README.
+    pub chart: bool,
+}
+
+impl ReportOptions {
+    /// Only the table of part timings.
+    pub fn plain() -> Self {
+        Self {
+            bars: false,
+            samples: false,
+            links: false,
+            ranking: 0,
+            chart: false,
+        }
+    }
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            bars: true,
+            samples: true,
+            links: true,
+            ranking: 5,
+            chart: true,
+        }
+    }
+}
+
+impl std::str::FromStr for ReportOptions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = Self::plain();
+
+        for section in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match section.split_once('=') {
+                None if section == "bars" => options.bars = true,
+                None if section == "samples" => options.samples = true,
+                None if section == "links" => options.links = true,
+                None if section == "chart" => options.chart = true,
+                None if section == "ranking" => options.ranking = Self::default().ranking,
+                Some(("ranking", n)) => {
+                    options.ranking = n.parse().map_err(|_| format!("invalid ranking `{n}`"))?;
+                }
+                _ => return Err(format!("unknown report section `{section}`")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ReportOptions;
+
+    #[test]
+    fn parses_report_options() {
+        assert_eq!("".parse(), Ok(ReportOptions::plain()));
+        assert_eq!(
+            "bars, ranking=3".parse(),
+            Ok(ReportOptions {
+                bars: true,
+                ranking: 3,
+                ..ReportOptions::plain()
+            })
+        );
+        assert_eq!(
+            "bars,samples,links,ranking,chart".parse(),
+            Ok(ReportOptions::default())
+        );
+        assert!("bars,pie".parse::<ReportOptions>().is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn env_value<T: std::str::FromStr>(key: &str) -> Option<T> {
+    env::var(key).ok().and_then(|x| x.parse().ok())
+}
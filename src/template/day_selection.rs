@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::day;
+use crate::template::{all_days, Day};
+
+/// A set of days, selected by a comma separated list of days and inclusive ranges.
+///
+/// Items prefixed with `!` remove days from the selection. A selection consisting only of
+/// removals starts out with every day. Ranges may be open on either side.
+///
+/// ```
+/// # use advent_of_code::template::{DaySelection, Day};
+/// let days = "1..10,!3".parse::<DaySelection>().unwrap().into_days();
+/// assert_eq!(days.len(), 9);
+/// assert!(!days.contains(&Day::new(3).unwrap()));
+///
+/// let days = "!25".parse::<DaySelection>().unwrap().into_days();
+/// assert_eq!(days.len(), 24);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(HashSet<Day>);
+
+impl DaySelection {
+    /// Converts the [`DaySelection`] into the selected days.
+    pub fn into_days(self) -> HashSet<Day> {
+        self.0
+    }
+}
+
+impl FromIterator<Day> for DaySelection {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Displays as a sorted list of days, e.g. `03,07,12`.
+impl Display for DaySelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut days: Vec<Day> = self.0.iter().copied().collect();
+        days.sort_unstable();
+
+        let days: Vec<String> = days.iter().map(Day::to_string).collect();
+        f.write_str(&days.join(","))
+    }
+}
+
+/// Parses a single day or a range like `3..7`, `..7` or `20..`.
+fn parse_item(item: &str) -> Result<Vec<Day>, DaySelectionFromStrError> {
+    let invalid = || DaySelectionFromStrError::InvalidItem(item.into());
+
+    match item.split_once("..") {
+        Some((start, end)) => {
+            let start = if start.is_empty() {
+                day!(1)
+            } else {
+                start.parse().map_err(|_| invalid())?
+            };
+            let end = if end.is_empty() {
+                day!(25)
+            } else {
+                end.parse().map_err(|_| invalid())?
+            };
+            if start > end {
+                return Err(DaySelectionFromStrError::ReversedRange(item.into()));
+            }
+            Ok(all_days()
+                .filter(|day| *day >= start && *day <= end)
+                .collect())
+        }
+        None => Ok(vec![item.parse().map_err(|_| invalid())?]),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut included = HashSet::new();
+        let mut excluded = HashSet::new();
+        let mut has_inclusions = false;
+
+        for item in s.split(',').map(str::trim) {
+            let (target, item) = match item.strip_prefix('!') {
+                Some(item) => (&mut excluded, item),
+                None => {
+                    has_inclusions = true;
+                    (&mut included, item)
+                }
+            };
+
+            target.extend(parse_item(item)?);
+        }
+
+        if !has_inclusions {
+            included = all_days().collect();
+        }
+
+        let days: HashSet<Day> = included.difference(&excluded).copied().collect();
+        if days.is_empty() {
+            return Err(DaySelectionFromStrError::Empty(s.into()));
+        }
+
+        Ok(Self(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub enum DaySelectionFromStrError {
+    /// An item is neither a day nor a range of days.
+    InvalidItem(String),
+    /// A range ends before it starts, e.g. `5..3`.
+    ReversedRange(String),
+    /// The selection removes every day it includes, e.g. `!1..25`.
+    Empty(String),
+}
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidItem(item) => write!(
+                f,
+                "invalid day selector `{item}`, expecting days between 1 and 25 such as `3`, `1..10` or `!25`"
+            ),
+            Self::ReversedRange(item) => write!(
+                f,
+                "invalid day range `{item}`, the first day must not come after the last"
+            ),
+            Self::Empty(selection) => {
+                write!(f, "day selection `{selection}` does not select any day")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::DaySelection;
+    use crate::template::{all_days, Day};
+
+    fn parse(s: &str) -> HashSet<u8> {
+        s.parse::<DaySelection>()
+            .unwrap()
+            .into_days()
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parses_lists_and_ranges() {
+        assert_eq!(parse("3,7,12"), HashSet::from([3, 7, 12]));
+        assert_eq!(parse("1..4"), HashSet::from([1, 2, 3, 4]));
+        assert_eq!(parse("23.."), HashSet::from([23, 24, 25]));
+        assert_eq!(parse("..2, 5"), HashSet::from([1, 2, 5]));
+    }
+
+    #[test]
+    fn parses_exclusions() {
+        assert_eq!(parse("!25").len(), 24);
+        assert!(!parse("!25").contains(&25));
+        assert_eq!(parse("1..5,!2..4"), HashSet::from([1, 5]));
+        assert_eq!(parse("!1..24"), HashSet::from([25]));
+        assert_eq!(parse("1..25").len(), all_days().count());
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        assert!("".parse::<DaySelection>().is_err());
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("1..26".parse::<DaySelection>().is_err());
+        assert!("3,,7".parse::<DaySelection>().is_err());
+        assert!("foo".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn rejects_empty_selections() {
+        assert!("5..3".parse::<DaySelection>().is_err());
+        assert!("!1..25".parse::<DaySelection>().is_err());
+        assert!("..,!..".parse::<DaySelection>().is_err());
+        assert!("3,!3".parse::<DaySelection>().is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
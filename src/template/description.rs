@@ -0,0 +1,303 @@
+/// Reads the cached markdown of a puzzle description: renders it for the terminal and extracts the
+/// examples and answers of each part.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Column the paragraphs of a description are wrapped at.
+const WRAP_WIDTH: usize = 80;
+
+/// A part of a puzzle description, starting at its heading, e.g. `--- Part Two ---`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartDescription {
+    pub title: String,
+    /// The first code block introduced by a paragraph mentioning an example.
+    pub example: Option<String>,
+    /// The answer given to the part, once it's solved.
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Code(String),
+}
+
+/// Splits markdown into headings, paragraphs and fenced code blocks.
+fn blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut code: Option<Vec<&str>> = None;
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        if let Some(lines) = &mut code {
+            if line == "```" {
+                blocks.push(Block::Code(lines.join("\n")));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+        } else if line == "```" {
+            flush(&mut paragraph, &mut blocks);
+            code = Some(vec![]);
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(heading.into()));
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Splits a description into its parts.
+pub fn parse(markdown: &str) -> Vec<PartDescription> {
+    let mut parts: Vec<PartDescription> = vec![];
+    let mut mentions_example = false;
+
+    for block in blocks(markdown) {
+        match block {
+            Block::Heading(heading) => {
+                parts.push(PartDescription {
+                    title: heading.trim_matches(['-', ' ']).into(),
+                    ..PartDescription::default()
+                });
+                mentions_example = false;
+            }
+            Block::Paragraph(text) => {
+                if let (Some(part), Some(answer)) = (
+                    parts.last_mut(),
+                    text.strip_prefix("Your puzzle answer was "),
+                ) {
+                    part.answer = Some(answer.trim_end_matches('.').replace(['`', '*'], ""));
+                }
+                mentions_example = text.to_lowercase().contains("example");
+            }
+            Block::Code(code) => {
+                if let Some(part) = parts.last_mut() {
+                    if mentions_example && part.example.is_none() {
+                        part.example = Some(code + "\n");
+                    }
+                }
+                mentions_example = false;
+            }
+        }
+    }
+
+    parts
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders a description for the terminal: headings and highlighted text in bold, inline code in
+/// italics, paragraphs wrapped and code blocks indented.
+pub fn render(markdown: &str) -> String {
+    let mut out = String::new();
+
+    for block in blocks(markdown) {
+        match block {
+            Block::Heading(heading) => {
+                out.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}\n"));
+            }
+            Block::Code(code) => {
+                for line in code.lines() {
+                    out.push_str(&format!("    {line}\n"));
+                }
+            }
+            Block::Paragraph(text) => {
+                // list items are separate lines, everything else is reflowed.
+                for item in split_items(&text) {
+                    let indent = if item.starts_with("- ") { "  " } else { "" };
+                    let wrapped = wrap(&strip_links(&item), WRAP_WIDTH, indent);
+                    out.push_str(&style(&wrapped));
+                    out.push('\n');
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn split_items(text: &str) -> Vec<String> {
+    let mut items: Vec<String> = vec![];
+
+    for line in text.lines() {
+        match items.last_mut() {
+            Some(item) if !line.starts_with("- ") => {
+                item.push(' ');
+                item.push_str(line);
+            }
+            _ => items.push(line.into()),
+        }
+    }
+
+    items
+}
+
+/// Replaces links like `[text](/2024/day/1)` with their text.
+fn strip_links(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('[') {
+        let link = rest[start..]
+            .find("](")
+            .and_then(|middle| Some((middle, rest[start + middle..].find(')')? + middle)));
+
+        match link {
+            Some((middle, end)) => {
+                out.push_str(&rest[..start]);
+                out.push_str(&rest[start + 1..start + middle]);
+                rest = &rest[start + end + 1..];
+            }
+            None => {
+                out.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+
+    out + rest
+}
+
+/// Wraps words at `width` visible characters, continuation lines start with `indent`.
+fn wrap(text: &str, width: usize, indent: &str) -> String {
+    let visible = |s: &str| s.chars().filter(|c| *c != '*' && *c != '`').count();
+
+    let mut out = String::new();
+    let mut line_width = 0;
+
+    for word in text.split_whitespace() {
+        if line_width > 0 && line_width + 1 + visible(word) > width {
+            out.push('\n');
+            out.push_str(indent);
+            line_width = indent.len();
+        } else if line_width > 0 {
+            out.push(' ');
+            line_width += 1;
+        }
+
+        out.push_str(word);
+        line_width += visible(word);
+    }
+
+    out
+}
+
+/// Turns `*highlighted*` text bold and `` `code` `` italic.
+fn style(text: &str) -> String {
+    let mut out = String::new();
+    let (mut bold, mut italic) = (false, false);
+
+    for c in text.chars() {
+        match c {
+            '*' => bold = !bold,
+            '`' => italic = !italic,
+            c => {
+                out.push(c);
+                continue;
+            }
+        }
+
+        out.push_str(ANSI_RESET);
+        if bold {
+            out.push_str(ANSI_BOLD);
+        }
+        if italic {
+            out.push_str(ANSI_ITALIC);
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, render, wrap, PartDescription};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const DESCRIPTION: &str = "## --- Day 3: Mull It Over ---
+
+The computer appears to be trying to run a program.
+
+For example, consider the following section of corrupted memory:
+
+```
+xmul(2,4)%&mul[3,7]
+
+!@^do_not_mul(5,5)
+```
+
+Adding up the result of each instruction produces *`161`*.
+
+Your puzzle answer was `170807108`.
+
+## --- Part Two ---
+
+Only the most recent instruction applies:
+
+```
+don't()
+```
+
+This time, the example is different:
+
+```
+xmul(2,4)&mul[3,7]!^don't()
+```
+";
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!(
+            parse(DESCRIPTION),
+            vec![
+                PartDescription {
+                    title: "Day 3: Mull It Over".into(),
+                    example: Some("xmul(2,4)%&mul[3,7]\n\n!@^do_not_mul(5,5)\n".into()),
+                    answer: Some("170807108".into()),
+                },
+                PartDescription {
+                    title: "Part Two".into(),
+                    example: Some("xmul(2,4)&mul[3,7]!^don't()\n".into()),
+                    answer: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let rendered = render("## --- Day 1 ---\n\nFind *all* the `#` [here](/2024/day/1).\n\n```\n..#\n```\n\n- one\n- two\n");
+        assert_eq!(
+            rendered,
+            format!(
+                "{ANSI_BOLD}--- Day 1 ---{ANSI_RESET}\n\n\
+                Find {ANSI_RESET}{ANSI_BOLD}all{ANSI_RESET} the {ANSI_RESET}{ANSI_ITALIC}#{ANSI_RESET} here.\n\n    \
+                ..#\n\n- one\n- two\n"
+            )
+        );
+    }
+
+    #[test]
+    fn wraps_words() {
+        assert_eq!(wrap("aaa bbb *ccc*", 7, ""), "aaa bbb\n*ccc*");
+        assert_eq!(wrap("- aaa bbb", 6, "  "), "- aaa\n  bbb");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
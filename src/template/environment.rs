@@ -0,0 +1,192 @@
+/// Fingerprint of the machine and build that produced a benchmark, stored with its timings.
+use std::{collections::HashMap, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+use crate::template::run_multi::Build;
+
+const UNKNOWN: &str = "unknown";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Environment {
+    pub cpu: String,
+    pub cores: usize,
+    /// Output of `rustc --version` of the compiler cargo built with.
+    pub rustc: String,
+    /// Cargo profile of the benched binary, e.g. `release` or `dhat`.
+    pub profile: String,
+    /// Cargo features the benched binary was built with.
+    pub features: Vec<String>,
+}
+
+impl Environment {
+    /// Fingerprint of this machine, for a run of the `all` binary built with `build`.
+    pub fn current(build: Build) -> Self {
+        Self {
+            cpu: cpu_model().unwrap_or_else(|| UNKNOWN.into()),
+            cores: thread::available_parallelism().map_or(0, |n| n.get()),
+            rustc: Some(env!("AOC_RUSTC_VERSION"))
+                .filter(|version| !version.is_empty())
+                .unwrap_or(UNKNOWN)
+                .into(),
+            profile: build.profile().into(),
+            features: build.features().iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    /// Human readable differences to `other`, e.g. `cores: 8 -> 16`.
+    pub fn differences(&self, other: &Self) -> Vec<String> {
+        let features = |env: &Self| match env.features.as_slice() {
+            [] => "none".to_string(),
+            features => features.join(","),
+        };
+
+        [
+            ("cpu", self.cpu.clone(), other.cpu.clone()),
+            ("cores", self.cores.to_string(), other.cores.to_string()),
+            ("rustc", self.rustc.clone(), other.rustc.clone()),
+            ("profile", self.profile.clone(), other.profile.clone()),
+            ("features", features(self), features(other)),
+        ]
+        .into_iter()
+        .filter(|(_, a, b)| a != b)
+        .map(|(key, a, b)| format!("{key}: {a} -> {b}"))
+        .collect()
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return cpuinfo
+            .lines()
+            .find_map(|line| line.strip_prefix("model name")?.split_once(':'))
+            .map(|(_, model)| model.trim().to_string());
+    }
+
+    // macOS doesn't have procfs.
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected environment.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|features| {
+                features
+                    .iter()
+                    .map(|f| f.get::<String>().cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("Expected environment.features to be an array of strings.")?;
+
+        Ok(Environment {
+            cpu: string("cpu")?,
+            cores: *cores as usize,
+            rustc: string("rustc")?,
+            profile: string("profile")?,
+            features,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Environment;
+
+    fn get_mock_environment() -> Environment {
+        Environment {
+            cpu: "AMD Ryzen 7 5800X 8-Core Processor".into(),
+            cores: 16,
+            rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+            profile: "release".into(),
+            features: vec![],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let environment = Environment {
+            features: vec!["count-allocs".into()],
+            ..get_mock_environment()
+        };
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json), Ok(environment));
+    }
+
+    #[test]
+    fn lists_differences() {
+        let before = get_mock_environment();
+        assert!(before.differences(&before).is_empty());
+
+        let after = Environment {
+            cores: 8,
+            features: vec!["count-allocs".into()],
+            ..get_mock_environment()
+        };
+        assert_eq!(
+            before.differences(&after),
+            vec!["cores: 16 -> 8", "features: none -> count-allocs"]
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
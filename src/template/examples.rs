@@ -0,0 +1,245 @@
+/// Manifest of example files and the answers they are expected to produce.
+/// The `solution!` macro generates a test that checks every part against the manifest.
+use std::{collections::HashMap, fmt::Display, fs, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::parse_answer;
+use crate::template::params::Parameters;
+use crate::template::{Day, Puzzle, Year};
+
+fn get_manifest_path(year: Year) -> String {
+    year.data_path("examples/manifest.json")
+}
+
+/// A single example file, e.g. `12-3` for `data/2024/examples/12-3.txt`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub day: Day,
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Values the puzzle states for the example only, e.g. the size of a smaller grid.
+    pub params: HashMap<String, String>,
+}
+
+impl Example {
+    /// Path of the example file of the given year.
+    pub fn path(&self, year: Year) -> String {
+        year.data_path(&format!("examples/{}.txt", self.file))
+    }
+
+    /// Reads the example file of the given year.
+    pub fn input(&self, year: Year) -> String {
+        let path = self.path(year);
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("could not open \"{path}\": {e}"))
+    }
+
+    /// Returns the expected answer of a part, if known.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Every example of a year with its expected answers.
+/// Can be deserialized from JSON, answers and parameters may be given as strings or integers:
+/// `{ "data": [{ "day": "14", "file": "14", "part_1": 12, "part_2": null, "params": { "w": 11, "h": 7 } }] }`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Examples {
+    pub data: Vec<Example>,
+}
+
+impl Examples {
+    /// Rehydrate the manifest of the given year. If not present, returns an empty manifest.
+    /// Panics if the manifest is malformed, so a typo doesn't silently skip tests.
+    pub fn read_from_file(year: Year) -> Self {
+        match fs::read_to_string(get_manifest_path(year)) {
+            Ok(json) => Examples::try_from(json)
+                .unwrap_or_else(|e| panic!("invalid example manifest of {year}: {e}")),
+            Err(_) => Examples::default(),
+        }
+    }
+
+    /// Returns the examples of a day.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = &Example> {
+        self.data.iter().filter(move |e| e.day == day)
+    }
+}
+
+/// Paths of the example files of a puzzle, e.g. `data/2024/examples/16-1.txt` and `16-2.txt`.
+pub fn example_files(puzzle: Puzzle) -> Vec<PathBuf> {
+    let folder = puzzle.year.data_path("examples");
+    let Ok(entries) = fs::read_dir(&folder) else {
+        return vec![];
+    };
+
+    let day = puzzle.day.to_string();
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str()?.strip_suffix(".txt"))
+                .and_then(|stem| stem.strip_prefix(day.as_str()))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+        })
+        .collect();
+    files.sort_unstable();
+    files
+}
+
+/// Runs a part on every example of the puzzle with an expected answer for that part,
+/// passing the parameters of the example.
+/// Panics if the manifest has no examples of the puzzle, so a missing or mistyped entry doesn't pass.
+pub fn check<P: Parameters, T: Display>(
+    puzzle: Puzzle,
+    part: u8,
+    func: impl Fn(&str, &P) -> Option<T>,
+) {
+    let examples = Examples::read_from_file(puzzle.year);
+    assert!(
+        examples.for_day(puzzle.day).next().is_some(),
+        "no examples of day {} in \"{}\"",
+        puzzle.day,
+        get_manifest_path(puzzle.year)
+    );
+
+    for example in examples.for_day(puzzle.day) {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+
+        let overrides = example.params.iter().map(|(n, v)| (n.as_str(), v.as_str()));
+        let params = P::with_overrides(overrides)
+            .unwrap_or_else(|e| panic!("invalid parameters of example {}: {e}", example.file));
+
+        let answer = func(&example.input(puzzle.year), &params).map(|answer| answer.to_string());
+        assert_eq!(
+            answer.as_deref(),
+            Some(expected),
+            "part {part} of example \"{}\"",
+            example.path(puzzle.year)
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Examples {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Examples {
+            data: json_data
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected example.day to be a Day struct.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        let part_1 = parse_answer(json.get("part_1")).or(Err(
+            "Expected example.part_1 to be null, string or integer.",
+        ))?;
+
+        let part_2 = parse_answer(json.get("part_2")).or(Err(
+            "Expected example.part_2 to be null, string or integer.",
+        ))?;
+
+        let params = match json.get("params") {
+            None => HashMap::new(),
+            Some(params) => params
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected example.params to be an object.")?
+                .iter()
+                .map(|(name, value)| match parse_answer(Some(value)) {
+                    Ok(Some(value)) => Ok((name.clone(), value)),
+                    _ => Err(format!(
+                        "Expected example.params.{name} to be a string or integer."
+                    )),
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Example {
+            day,
+            file: file.clone(),
+            part_1,
+            part_2,
+            params,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Examples;
+    use crate::day;
+
+    #[test]
+    fn handles_json_manifest() {
+        let json = r#"{ "data": [
+            { "day": "12", "file": "12-1", "part_1": 140, "part_2": "80" },
+            { "day": "14", "file": "14", "part_1": 12, "params": { "w": 11, "h": "7" } }
+        ] }"#
+            .to_string();
+        let examples = Examples::try_from(json).unwrap();
+
+        let example = examples.for_day(day!(12)).next().unwrap();
+        assert_eq!(example.file, "12-1");
+        assert_eq!(example.expected(1), Some("140"));
+        assert_eq!(example.expected(2), Some("80"));
+        assert!(example.params.is_empty());
+
+        let example = examples.for_day(day!(14)).next().unwrap();
+        assert_eq!(example.expected(2), None);
+        assert_eq!(example.params["w"], "11");
+        assert_eq!(example.params["h"], "7");
+        assert_eq!(examples.for_day(day!(3)).count(), 0);
+    }
+
+    #[test]
+    fn rejects_malformed_manifests() {
+        let json = r#"{ "data": [{ "day": "01", "file": "01", "params": { "w": [1] } }] }"#;
+        assert!(Examples::try_from(json.to_string()).is_err());
+
+        let json = r#"{ "data": [{ "day": "01", "part_1": 1 }] }"#;
+        assert!(Examples::try_from(json.to_string()).is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,276 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    timings::{Timing, Timings},
+    Day, Year,
+};
+
+fn get_history_path(year: Year) -> String {
+    year.data_path("timings_history.json")
+}
+
+/// Benchmark times of a single day, recorded by one run of `cargo time --store`.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Output of `git describe --always --dirty` at the time of the run, if available.
+    pub commit: Option<String>,
+    pub timing: Timing,
+}
+
+/// Represents all recorded benchmark runs of a year, oldest first.
+/// Can be serialized to and deserialized from JSON:
+/// `{ "data": [{ "timestamp": 1733400000, "commit": "a1b2c3d", "timing": { "day": "01", ... } }] }`
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub data: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Serialize history as JSON and write it to the history file of the given year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_history_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate history from the JSON file of the given year. If not present, returns an empty history.
+    /// A file that can't be read or parsed is an error, storing over it would erase the history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_history_path(year);
+        match fs::read_to_string(&path) {
+            Ok(content) => History::try_from(content).map_err(|e| format!("{path}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{path}: {e}")),
+        }
+    }
+
+    /// Append the timings of a run, stamped with the current time and git commit.
+    pub fn record(&mut self, timings: &Timings) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let commit = current_commit();
+
+        self.data
+            .extend(timings.data.iter().map(|timing| HistoryEntry {
+                timestamp,
+                commit: commit.clone(),
+                timing: timing.clone(),
+            }));
+    }
+
+    /// Recorded runs of a single day, oldest first.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = &HistoryEntry> {
+        self.data
+            .iter()
+            .filter(move |entry| entry.timing.day == day)
+    }
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_string()).filter(|c| !c.is_empty())
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let minutes = (timestamp % 86400) / 60;
+
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            data: json_data
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = match json.get("commit") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(s)) => Some(s.clone()),
+            Some(_) => return Err("Expected history.commit to be null or a string.".into()),
+        };
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected history entry to have key `timing`.")?;
+
+        Ok(HistoryEntry {
+            timestamp: *timestamp as u64,
+            commit,
+            timing: Timing::try_from(timing)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, History, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(PartTiming::Legacy(Duration::from_millis(10))),
+                part_2: None,
+                part_1_heap: None,
+                part_2_heap: None,
+                total_nanos: 1e+7,
+                environment: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn records_runs() {
+        let mut history = History::default();
+        history.record(&get_mock_timings());
+        history.record(&get_mock_timings());
+
+        assert_eq!(history.data.len(), 2);
+        assert_eq!(history.for_day(day!(1)).count(), 2);
+        assert_eq!(history.for_day(day!(2)).count(), 0);
+        assert!(history.data[0].timestamp > 0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = History {
+            data: vec![HistoryEntry {
+                timestamp: 1_733_400_000,
+                commit: Some("a1b2c3d".into()),
+                timing: get_mock_timings().data[0].clone(),
+            }],
+        };
+
+        let json = JsonValue::from(&history).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+        assert_eq!(parsed.data[0].timestamp, 1_733_400_000);
+        assert_eq!(parsed.data[0].commit.as_deref(), Some("a1b2c3d"));
+        assert_eq!(
+            parsed.data[0].timing.part_duration(1),
+            Some(Duration::from_millis(10))
+        );
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_400_000), "2024-12-05 12:00");
+        assert_eq!(format_timestamp(951_825_600), "2000-02-29 12:00");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
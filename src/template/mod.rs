@@ -1,15 +1,35 @@
 use std::{env, fs};
 
+pub mod alloc_counter;
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod params;
+pub mod provider;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use day_selection::*;
+pub use output::*;
+pub use puzzle::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod day_selection;
+mod description;
+mod environment;
+mod history;
+mod output;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +37,137 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: Puzzle) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: Puzzle, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.data_part_path(folder, part, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, `params: Params` passes a set of [`params::Parameters`] to both parts as a second
+/// argument, overridden by `--param` and by the example manifest.
+///
+/// A test checking each part against the example manifest is generated as well, e.g.
+/// `example_manifest::test_part_one`, see [`examples::check`].
+///
+/// When prefixed with `@registry`, no `main` is generated. Instead, the parts are exposed as the
+/// constant `SOLUTION`, which `build.rs` uses to compile every day into the `all` binary. The
+/// registry runs parts with their default parameters.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl main, $year, $day, plain (), [part_one, 1, test_part_one] [part_two, 2, test_part_two]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl main, $year, $day, plain (), [part_one, 1, test_part_one]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl main, $year, $day, plain (), [part_two, 2, test_part_two]);
+    };
+    ($year:expr, $day:expr, params: $params:ty) => {
+        $crate::solution!(@impl main, $year, $day, params $params, [part_one, 1, test_part_one] [part_two, 2, test_part_two]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@registry $year:expr, $day:expr) => {
+        $crate::solution!(@impl registry, $year, $day, plain (), [part_one, 1] [part_two, 2]);
+    };
+    (@registry $year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl registry, $year, $day, plain (), [part_one, 1]);
+    };
+    (@registry $year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl registry, $year, $day, plain (), [part_two, 2]);
+    };
+    (@registry $year:expr, $day:expr, params: $params:ty) => {
+        $crate::solution!(@impl registry, $year, $day, params $params, [part_one, 1] [part_two, 2]);
+    };
+
+    (@impl main, $year:expr, $day:expr, $mode:ident $params:ty, $( [$func:ident, $part:expr, $test:ident] )*) => {
+        $crate::solution!(@constants $year, $day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc_counter::CountingAlloc =
+            $crate::template::alloc_counter::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let args = $crate::template::cli::SolutionArgs::from_env();
+            let params: $params = read_params(&args);
+            // NOTE: leaked, parts are executed on their own thread (see `runner::run_isolated`).
+            let input: &'static str = read_input(PUZZLE, args.input.as_deref()).leak();
+            let parts = vec![$(
+                run_part(
+                    move |input| $crate::solution!(@call $mode, $func, input, &params),
+                    input,
+                    PUZZLE,
+                    $part,
+                    &args,
+                ),
+            )*];
+            report_results(PUZZLE, parts, &args);
+        }
+
+        #[cfg(test)]
+        mod example_manifest {
+            use super::*;
+
+            $(
+                /// Checks the part against the examples in `data/{year}/examples/manifest.json`.
+                #[test]
+                fn $test() {
+                    $crate::template::examples::check(PUZZLE, $part, |input, params: &$params| {
+                        $crate::solution!(@call $mode, $func, input, params)
+                    });
+                }
+            )*
         }
     };
+
+    (@impl registry, $year:expr, $day:expr, $mode:ident $params:ty, $( [$func:ident, $part:expr] )*) => {
+        $crate::solution!(@constants $year, $day);
+
+        /// The registry entry of the current puzzle.
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: PUZZLE,
+            parts: &[ $( ($part, |input| {
+                $crate::solution!(@call $mode, $func, input, &<$params>::default())
+                    .map(|answer| answer.to_string())
+            }), )* ],
+        };
+    };
+
+    (@call plain, $func:ident, $input:expr, $params:expr) => {{
+        let _ = $params;
+        $func($input)
+    }};
+    (@call params, $func:ident, $input:expr, $params:expr) => {
+        $func($input, $params)
+    };
+
+    (@constants $year:expr, $day:expr) => {
+        /// The current year.
+        const YEAR: $crate::template::Year = $crate::year!($year);
+
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The current puzzle.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new(YEAR, DAY);
+    };
 }
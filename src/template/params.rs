@@ -0,0 +1,145 @@
+//! Typed parameters of a solution, for puzzles whose examples use e.g. a smaller grid than the real input.
+//! Declared with [`parameters!`](crate::parameters), overridden with `cargo solve <day> --param name=value`
+//! and by the `params` of the example manifest.
+
+/// A set of named parameters with defaults for the real input.
+pub trait Parameters: Copy + Default + Send + 'static {
+    /// Names of all parameters, in declaration order.
+    const NAMES: &'static [&'static str];
+
+    /// Overrides a single parameter, parsing `value` to the type of the parameter.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    /// The defaults with the given overrides applied.
+    fn with_overrides<'a>(
+        overrides: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (name, value) in overrides {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Solutions without parameters.
+impl Parameters for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(unknown_parameter(name, Self::NAMES))
+    }
+}
+
+/// The error of setting a parameter that isn't declared.
+pub fn unknown_parameter(name: &str, names: &[&str]) -> String {
+    if names.is_empty() {
+        format!("unknown parameter `{name}`, the solution has no parameters")
+    } else {
+        format!(
+            "unknown parameter `{name}`, expected one of: {}",
+            names.join(", ")
+        )
+    }
+}
+
+/// Declares a struct of solution parameters and their defaults, implementing [`Parameters`].
+///
+/// ```ignore
+/// advent_of_code::parameters! {
+///     pub struct Params {
+///         /// Width of the room.
+///         w: i32 = 101,
+///         h: i32 = 103,
+///     }
+/// }
+///
+/// advent_of_code::solution!(2024, 14, params: Params);
+///
+/// pub fn part_one(input: &str, params: &Params) -> Option<u32> { ... }
+/// ```
+#[macro_export]
+macro_rules! parameters {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident : $ty:ty = $default:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        $vis struct $name {
+            $( $(#[$field_meta])* $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $( $field: $default, )* }
+            }
+        }
+
+        impl $crate::template::params::Parameters for $name {
+            const NAMES: &'static [&'static str] = &[$( stringify!($field), )*];
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value.parse().map_err(|_| {
+                                format!("invalid value `{value}` for parameter `{name}`")
+                            })?;
+                        }
+                    )*
+                    _ => {
+                        return Err($crate::template::params::unknown_parameter(name, Self::NAMES))
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Parameters;
+
+    crate::parameters! {
+        struct Params {
+            size: i32 = 70,
+            bytes: usize = 1024,
+        }
+    }
+
+    #[test]
+    fn applies_overrides() {
+        assert_eq!(
+            Params::with_overrides([]),
+            Ok(Params {
+                size: 70,
+                bytes: 1024
+            })
+        );
+        assert_eq!(
+            Params::with_overrides([("size", "6"), ("bytes", "12")]),
+            Ok(Params { size: 6, bytes: 12 })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        assert_eq!(
+            Params::with_overrides([("width", "6")]),
+            Err("unknown parameter `width`, expected one of: size, bytes".into())
+        );
+        assert_eq!(
+            Params::with_overrides([("size", "-")]),
+            Err("invalid value `-` for parameter `size`".into())
+        );
+        assert!(<()>::with_overrides([("size", "6")]).is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
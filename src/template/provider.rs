@@ -0,0 +1,627 @@
+/// Native access to puzzle inputs and descriptions.
+/// The [`HttpFetcher`] downloads them from the advent of code website, the [`FileCache`] in front of it
+/// keeps them in `data/{year}` and verifies cached files against recorded checksums.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{config::Config, Puzzle};
+
+/// Environment variable holding the session token, i.e. the value of the `session` cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// File in the home directory holding the session token, shared with aoc-cli.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+/// Website of Advent of Code, puzzles are at `/{year}/day/{day}`.
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Identifies downloads by the project the template comes from, the website asks automated tools to
+/// include contact info.
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/fspoettel/advent-of-code-rust)"
+);
+
+#[derive(Debug)]
+pub enum ProviderError {
+    MissingSession,
+    BadStatus(u16),
+    Network(String),
+    IO(io::Error),
+    /// The recorded checksums can't be parsed, with the path and cause.
+    Checksums(String),
+}
+
+impl Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderError::MissingSession => write!(
+                f,
+                "no session token, set {SESSION_ENV} or write it to ~/{SESSION_FILE}."
+            ),
+            ProviderError::BadStatus(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            ProviderError::Network(e) => write!(f, "request failed: {e}"),
+            ProviderError::IO(e) => write!(f, "{e}"),
+            ProviderError::Checksums(e) => write!(f, "invalid checksums {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for ProviderError {
+    fn from(e: io::Error) -> Self {
+        ProviderError::IO(e)
+    }
+}
+
+/// A source of puzzle inputs and descriptions.
+pub trait InputProvider {
+    /// The personal input of a puzzle.
+    fn input(&self, puzzle: Puzzle) -> Result<String, ProviderError>;
+
+    /// The description of a puzzle, as markdown.
+    fn description(&self, puzzle: Puzzle) -> Result<String, ProviderError>;
+}
+
+/// A file a [`FileCache`] manages for each puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    Input,
+    Description,
+}
+
+impl Resource {
+    fn folder(self) -> &'static str {
+        match self {
+            Resource::Input => "inputs",
+            Resource::Description => "puzzles",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Resource::Input => "txt",
+            Resource::Description => "md",
+        }
+    }
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::Input => f.write_str("input"),
+            Resource::Description => f.write_str("puzzle"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Downloads inputs and descriptions over HTTP, authenticated by a session token.
+pub struct HttpFetcher {
+    base_url: String,
+    session: Option<String>,
+    user_agent: String,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session,
+            user_agent: USER_AGENT.into(),
+        }
+    }
+
+    /// Fetches from the advent of code website, with the session token from the environment or
+    /// the session file and the configured contact info.
+    pub fn from_env() -> Self {
+        Self::new(BASE_URL, read_session()).with_contact(Config::from_env().contact.as_deref())
+    }
+
+    /// Adds contact info of the user, e.g. an email address, to the user agent.
+    pub fn with_contact(mut self, contact: Option<&str>) -> Self {
+        if let Some(contact) = contact {
+            self.user_agent = format!("{USER_AGENT} {contact}");
+        }
+        self
+    }
+
+    fn get(&self, path: &str) -> Result<String, ProviderError> {
+        let session = self
+            .session
+            .as_deref()
+            .ok_or(ProviderError::MissingSession)?;
+
+        let response = ureq::get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", &self.user_agent)
+            .timeout(Duration::from_secs(30))
+            .call();
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, _)) => Err(ProviderError::BadStatus(status)),
+            Err(e) => Err(ProviderError::Network(e.to_string())),
+        }
+    }
+}
+
+impl InputProvider for HttpFetcher {
+    fn input(&self, puzzle: Puzzle) -> Result<String, ProviderError> {
+        self.get(&format!(
+            "/{}/day/{}/input",
+            puzzle.year,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    fn description(&self, puzzle: Puzzle) -> Result<String, ProviderError> {
+        let html = self.get(&format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner()))?;
+        Ok(html_to_markdown(&html))
+    }
+}
+
+fn read_session() -> Option<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .or_else(|| {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            fs::read_to_string(Path::new(&home).join(SESSION_FILE)).ok()
+        })
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Keeps inputs and descriptions in `{root}/{year}/{inputs,puzzles}` and only asks its source for
+/// files that are missing, files modified since they were downloaded are reported.
+/// Checksums of downloaded files are recorded in `{root}/{year}/checksums.json`.
+pub struct FileCache<P> {
+    root: PathBuf,
+    source: P,
+}
+
+impl FileCache<HttpFetcher> {
+    /// The cache in `data`, downloading from the advent of code website.
+    pub fn from_env() -> Self {
+        Self::new("data", HttpFetcher::from_env())
+    }
+}
+
+impl<P: InputProvider> FileCache<P> {
+    pub fn new(root: impl Into<PathBuf>, source: P) -> Self {
+        Self {
+            root: root.into(),
+            source,
+        }
+    }
+
+    /// Path of a cached file, e.g. `data/2024/inputs/08.txt`.
+    pub fn path(&self, puzzle: Puzzle, resource: Resource) -> PathBuf {
+        self.root
+            .join(puzzle.year.to_string())
+            .join(resource.folder())
+            .join(format!("{}.{}", puzzle.day, resource.extension()))
+    }
+
+    /// Returns the cached file, fetching it if it's missing.
+    /// A file modified since it was downloaded is kept, with a warning, as the edit may be deliberate.
+    /// Files without a recorded checksum, e.g. placed by hand, are trusted and recorded.
+    pub fn get(&self, puzzle: Puzzle, resource: Resource) -> Result<String, ProviderError> {
+        let path = self.path(puzzle, resource);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return self.refresh(puzzle, resource);
+        };
+
+        let mut checksums = self.read_checksums(puzzle)?;
+        let key = checksum_key(puzzle, resource);
+
+        match checksums.get(&key) {
+            Some(recorded) if *recorded == checksum(&content) => Ok(content),
+            Some(_) => {
+                eprintln!(
+                    "Warning: cached {resource} \"{}\" was modified since it was downloaded, \
+                    use `download {} --force` to download it again.",
+                    path.display(),
+                    puzzle.day
+                );
+                Ok(content)
+            }
+            None => {
+                checksums.insert(key, checksum(&content));
+                self.store_checksums(puzzle, &checksums)?;
+                Ok(content)
+            }
+        }
+    }
+
+    /// Fetches a file from the source and overwrites the cached copy.
+    pub fn refresh(&self, puzzle: Puzzle, resource: Resource) -> Result<String, ProviderError> {
+        let mut checksums = self.read_checksums(puzzle)?;
+        let content = match resource {
+            Resource::Input => self.source.input(puzzle)?,
+            Resource::Description => self.source.description(puzzle)?,
+        };
+
+        let path = self.path(puzzle, resource);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &content)?;
+
+        checksums.insert(checksum_key(puzzle, resource), checksum(&content));
+        self.store_checksums(puzzle, &checksums)?;
+
+        Ok(content)
+    }
+
+    fn checksums_path(&self, puzzle: Puzzle) -> PathBuf {
+        self.root
+            .join(puzzle.year.to_string())
+            .join("checksums.json")
+    }
+
+    /// Reads the recorded checksums. If not present, none are recorded. A file that can't be parsed
+    /// is an error, storing over it would drop the checksums of every other file.
+    fn read_checksums(&self, puzzle: Puzzle) -> Result<HashMap<String, String>, ProviderError> {
+        let path = self.checksums_path(puzzle);
+        match fs::read_to_string(&path) {
+            Ok(content) => parse_checksums(content)
+                .map_err(|e| ProviderError::Checksums(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn store_checksums(
+        &self,
+        puzzle: Puzzle,
+        checksums: &HashMap<String, String>,
+    ) -> Result<(), ProviderError> {
+        let mut entries: Vec<_> = checksums.iter().collect();
+        entries.sort_unstable();
+
+        let data = entries
+            .into_iter()
+            .map(|(file, checksum)| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("file".into(), JsonValue::String(file.clone()));
+                map.insert("checksum".into(), JsonValue::String(checksum.clone()));
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+
+        let mut file = fs::File::create(self.checksums_path(puzzle))?;
+        Ok(JsonValue::Object(map).format_to(&mut file)?)
+    }
+}
+
+impl<P: InputProvider> InputProvider for FileCache<P> {
+    fn input(&self, puzzle: Puzzle) -> Result<String, ProviderError> {
+        self.get(puzzle, Resource::Input)
+    }
+
+    fn description(&self, puzzle: Puzzle) -> Result<String, ProviderError> {
+        self.get(puzzle, Resource::Description)
+    }
+}
+
+fn checksum_key(puzzle: Puzzle, resource: Resource) -> String {
+    format!(
+        "{}/{}.{}",
+        resource.folder(),
+        puzzle.day,
+        resource.extension()
+    )
+}
+
+/// FNV-1a hash of the content. Guards against accidental modification, not tampering.
+fn checksum(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("fnv1a64:{hash:016x}")
+}
+
+/// Parses `{ "data": [{ "file": "inputs/01.txt", "checksum": "fnv1a64:..." }] }`.
+fn parse_checksums(value: String) -> Result<HashMap<String, String>, String> {
+    let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+    let json_data = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get("data")
+        .ok_or("expected JSON document to have key `data`.")?
+        .get::<Vec<JsonValue>>()
+        .ok_or("expected `json.data` to be an array.")?;
+
+    json_data
+        .iter()
+        .map(|entry| {
+            let entry = entry
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected checksum to be a JSON object.")?;
+            let field = |key: &str| {
+                entry
+                    .get(key)
+                    .and_then(|v| v.get::<String>())
+                    .cloned()
+                    .ok_or(format!("Expected checksum.{key} to be a string."))
+            };
+            Ok((field("file")?, field("checksum")?))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the `<article>` elements of a puzzle page to markdown, each followed by the answer
+/// given to its part, e.g. `Your puzzle answer was `1234`.`
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        let body = &article[article.find('>').map_or(0, |i| i + 1)..end];
+        markdown.push_str(&convert_article(body));
+        rest = &article[end..];
+
+        let next = rest.find("<article").unwrap_or(rest.len());
+        if let Some(answer) = rest[..next].find("<p>Your puzzle answer was") {
+            let answer = &rest[answer..next];
+            let end = answer
+                .find("</p>")
+                .map_or(answer.len(), |i| i + "</p>".len());
+            markdown.push_str(&convert_article(&answer[..end]));
+        }
+    }
+
+    // collapse the blank lines left over from the markup.
+    let mut result = String::new();
+    let mut newlines = 0;
+    for c in markdown.trim().chars() {
+        newlines = if c == '\n' { newlines + 1 } else { 0 };
+        if newlines <= 2 {
+            result.push(c);
+        }
+    }
+
+    result + "\n"
+}
+
+fn convert_article(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2", true) | ("p", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("ul", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => out.push_str(&format!("]({})", links.pop().unwrap_or_default())),
+            _ => {}
+        }
+    }
+
+    out.push_str(&decode_entities(rest));
+    out
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        cell::Cell,
+        env, fs,
+        io::{Read, Write},
+        net::TcpListener,
+        process, thread,
+    };
+
+    use super::{html_to_markdown, FileCache, HttpFetcher, InputProvider, ProviderError, Resource};
+    use crate::template::Puzzle;
+    use crate::{day, year};
+
+    const PUZZLE: Puzzle = Puzzle::new(year!(2024), day!(8));
+
+    /// Serves a single request with the given status and body, returns the base url and a handle
+    /// resolving to the raw request.
+    fn mock_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0; 4096];
+            let len = stream.read(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            String::from_utf8_lossy(&request[..len]).to_string()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, server) = mock_server("200 OK", "1 2 3\n");
+        let fetcher = HttpFetcher::new(&base_url, Some("secret".into()));
+
+        assert_eq!(fetcher.input(PUZZLE).unwrap(), "1 2 3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/8/input HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+        assert!(request.contains("(+https://github.com/fspoettel/advent-of-code-rust)"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = mock_server("404 Not Found", "");
+        let fetcher = HttpFetcher::new(&base_url, Some("secret".into()));
+
+        assert!(matches!(
+            fetcher.input(PUZZLE),
+            Err(ProviderError::BadStatus(404))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn requires_session() {
+        let fetcher = HttpFetcher::new("http://127.0.0.1:1", None);
+        assert!(matches!(
+            fetcher.input(PUZZLE),
+            Err(ProviderError::MissingSession)
+        ));
+    }
+
+    struct CountingProvider {
+        calls: Cell<u32>,
+    }
+
+    impl InputProvider for CountingProvider {
+        fn input(&self, _: Puzzle) -> Result<String, ProviderError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok("input".into())
+        }
+
+        fn description(&self, _: Puzzle) -> Result<String, ProviderError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok("## Day 8".into())
+        }
+    }
+
+    #[test]
+    fn caches_and_verifies_files() {
+        let root = env::temp_dir().join(format!("aoc-cache-{}", process::id()));
+        let cache = FileCache::new(
+            &root,
+            CountingProvider {
+                calls: Cell::new(0),
+            },
+        );
+
+        assert_eq!(cache.input(PUZZLE).unwrap(), "input");
+        assert_eq!(cache.input(PUZZLE).unwrap(), "input");
+        assert_eq!(cache.source.calls.get(), 1);
+
+        let path = cache.path(PUZZLE, Resource::Input);
+        assert_eq!(path, root.join("2024/inputs/08.txt"));
+
+        // a modified file is kept, unless downloaded again explicitly.
+        fs::write(&path, "modified").unwrap();
+        assert_eq!(cache.input(PUZZLE).unwrap(), "modified");
+        assert_eq!(cache.source.calls.get(), 1);
+        assert_eq!(cache.refresh(PUZZLE, Resource::Input).unwrap(), "input");
+        assert_eq!(cache.source.calls.get(), 2);
+
+        assert_eq!(cache.description(PUZZLE).unwrap(), "## Day 8");
+        assert_eq!(cache.source.calls.get(), 3);
+
+        // malformed checksums are reported rather than overwritten.
+        let checksums = root.join("2024/checksums.json");
+        fs::write(&checksums, "{ \"data\": [").unwrap();
+        assert!(matches!(
+            cache.refresh(PUZZLE, Resource::Input),
+            Err(ProviderError::Checksums(_))
+        ));
+        assert_eq!(fs::read_to_string(&checksums).unwrap(), "{ \"data\": [");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn converts_html_to_markdown() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 8: Test ---</h2>
+<p>Find <em>all</em> the <code>#</code> &amp; <a href="/2024/day/7">antennas</a>:</p>
+<pre><code>..#
+<em>a</em>..
+</code></pre>
+<ul><li>one</li><li>two</li></ul></article><p>Not part of the puzzle.</p>
+<p>Your puzzle answer was <code>42</code>.</p></main>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 8: Test ---\n\nFind *all* the `#` & [antennas](/2024/day/7):\n\n```\n..#\na..\n```\n\n- one\n- two\n\nYour puzzle answer was `42`.\n"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use crate::template::provider::BASE_URL;
+use crate::template::{Day, Year};
+
+/// Identifies a single puzzle of a specific advent.
+///
+/// # Display
+/// This value displays as `{year}-{day}`, which is also the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle, Year};
+/// let puzzle = Puzzle::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a per-day data file, e.g. `data/2024/inputs/08.txt`.
+    #[must_use]
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        self.year
+            .data_path(&format!("{folder}/{}.{extension}", self.day))
+    }
+
+    /// Path of a per-part data file, e.g. `data/2024/examples/08-2.txt`.
+    #[must_use]
+    pub fn data_part_path(&self, folder: &str, part: u8, extension: &str) -> String {
+        self.year
+            .data_path(&format!("{folder}/{}-{part}.{extension}", self.day))
+    }
+
+    /// Path of the source file of the solution binary, e.g. `src/bin/2024-08.rs`.
+    #[must_use]
+    pub fn bin_path(&self) -> String {
+        format!("src/bin/{self}.rs")
+    }
+
+    /// The puzzle page, e.g. `https://adventofcode.com/2024/day/8`.
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("{BASE_URL}/{}/day/{}", self.year, self.day.into_inner())
+    }
+}
+
+#[cfg(feature = "today")]
+impl Puzzle {
+    /// Returns today's puzzle if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Puzzle;
+    use crate::{day, year};
+
+    #[test]
+    fn formats_paths() {
+        let puzzle = Puzzle::new(year!(2024), day!(8));
+        assert_eq!(puzzle.to_string(), "2024-08");
+        assert_eq!(puzzle.bin_path(), "src/bin/2024-08.rs");
+        assert_eq!(puzzle.data_path("inputs", "txt"), "data/2024/inputs/08.txt");
+        assert_eq!(
+            puzzle.data_part_path("examples", 2, "txt"),
+            "data/2024/examples/08-2.txt"
+        );
+        assert_eq!(puzzle.url(), "https://adventofcode.com/2024/day/8");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
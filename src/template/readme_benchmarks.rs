@@ -2,8 +2,9 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::config::{Config, ReportOptions};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Puzzle, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +27,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: Puzzle) -> String {
+    format!("./{}", puzzle.bin_path())
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,76 +53,269 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Width of the relative-cost bar of a day, in characters.
+const BAR_WIDTH: usize = 10;
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    options: &ReportOptions,
+) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if options.chart {
+        lines.push(format!("![{year} benchmarks](./{})", chart_path(year)));
+        lines.push(String::new());
+    }
+
+    let mut columns = vec!["Day", "Part 1", "Part 2"];
+    if options.samples {
+        columns.push("Samples");
+    }
+    if options.bars {
+        columns.push("Relative cost");
+    }
+    if options.links {
+        columns.push("Puzzle");
+    }
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("|{}", " :---: |".repeat(columns.len())));
+
+    let slowest_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0_f64, f64::max);
+
+    for timing in &timings.data {
+        let puzzle = Puzzle::new(year, timing.day);
+        let mut cells = vec![
+            format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                get_path_for_bin(puzzle)
+            ),
+            format!("`{}`", timing.part_1.map_or("-".into(), |t| t.to_string())),
+            format!("`{}`", timing.part_2.map_or("-".into(), |t| t.to_string())),
+        ];
+
+        if options.samples {
+            let samples = [timing.part_1, timing.part_2].map(|part| match part {
+                Some(part) if part.samples() > 0 => part.samples().to_string(),
+                _ => "-".into(),
+            });
+            cells.push(samples.join(" / "));
+        }
+        if options.bars {
+            cells.push(format!(
+                "`{}`",
+                relative_bar(timing.total_nanos / slowest_nanos.max(1.0))
+            ));
+        }
+        if options.links {
+            cells.push(format!("[adventofcode.com]({})", puzzle.url()));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if options.ranking > 0 && !timings.data.is_empty() {
+        let mut ranking: Vec<_> = timings.data.iter().collect();
+        ranking.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+        let total_nanos = ranking.iter().map(|t| t.total_nanos).sum::<f64>().max(1.0);
+
+        lines.push(String::new());
+        lines.push("**Slowest days:**".into());
+        lines.push(String::new());
+
+        for (rank, timing) in ranking.iter().take(options.ranking).enumerate() {
+            let millis = timing.total_nanos / 1_000_000_f64;
+            lines.push(format!(
+                "{}. Day {}: `{millis:.2}ms` ({:.1}% of total)",
+                rank + 1,
+                timing.day.into_inner(),
+                timing.total_nanos / total_nanos * 100.0
+            ));
+        }
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+/// A bar of [`BAR_WIDTH`] characters, filled to `fraction`. Days with any cost get at least one block.
+fn relative_bar(fraction: f64) -> String {
+    let filled = ((fraction * BAR_WIDTH as f64).round() as usize).clamp(1, BAR_WIDTH);
+    "█".repeat(filled) + &"░".repeat(BAR_WIDTH - filled)
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    options: &ReportOptions,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", year, timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
+    let options = Config::from_env().readme_report;
+
+    if options.chart {
+        fs::write(chart_path(year), construct_chart(year, &timings))?;
+    }
+
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis, &options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/* -------------------------------------------------------------------------- */
+
+/// Path of the benchmark chart of a year, next to the README.
+fn chart_path(year: Year) -> String {
+    format!("benchmarks-{year}.svg")
+}
+
+const CHART_PADDING: f64 = 10.0;
+const CHART_ROW_HEIGHT: f64 = 20.0;
+const CHART_LABEL_WIDTH: f64 = 60.0;
+const CHART_BAR_WIDTH: f64 = 400.0;
+const CHART_VALUE_WIDTH: f64 = 80.0;
+const CHART_COLORS: [&str; 2] = ["#4c8eda", "#f5a623"];
+
+/// Renders a horizontal bar chart of the timings, one row per day with its parts stacked.
+fn construct_chart(year: Year, timings: &Timings) -> String {
+    let part_nanos = |timing: &Timing| {
+        [1, 2].map(|part| {
+            timing
+                .part_duration(part)
+                .map_or(0_f64, |d| d.as_nanos() as f64)
+        })
+    };
+
+    let slowest_nanos = timings
+        .data
+        .iter()
+        .map(|t| part_nanos(t).iter().sum::<f64>())
+        .fold(0_f64, f64::max)
+        .max(1.0);
+
+    let width = 2.0 * CHART_PADDING + CHART_LABEL_WIDTH + CHART_BAR_WIDTH + CHART_VALUE_WIDTH;
+    let height = 2.0 * CHART_PADDING + (timings.data.len() + 1) as f64 * CHART_ROW_HEIGHT;
+
+    let mut lines = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="monospace" font-size="12">"#
+        ),
+        format!("<title>{year} Benchmarks</title>"),
+        format!(r#"<rect width="{width}" height="{height}" fill="white"/>"#),
+    ];
+
+    // legend in the first row.
+    let mut x = CHART_PADDING + CHART_LABEL_WIDTH;
+    for (part, color) in CHART_COLORS.iter().enumerate() {
+        lines.push(format!(
+            r#"<rect x="{x}" y="{}" width="10" height="10" fill="{color}"/>"#,
+            CHART_PADDING + 4.0
+        ));
+        lines.push(format!(
+            r#"<text x="{}" y="{}">Part {}</text>"#,
+            x + 14.0,
+            CHART_PADDING + 13.0,
+            part + 1
+        ));
+        x += 80.0;
+    }
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        let y = CHART_PADDING + (row + 1) as f64 * CHART_ROW_HEIGHT;
+
+        lines.push(format!(
+            r#"<text x="{CHART_PADDING}" y="{}">Day {}</text>"#,
+            y + 13.0,
+            timing.day.into_inner()
+        ));
+
+        let mut x = CHART_PADDING + CHART_LABEL_WIDTH;
+        for (nanos, color) in part_nanos(timing).iter().zip(CHART_COLORS) {
+            let bar_width = nanos / slowest_nanos * CHART_BAR_WIDTH;
+            lines.push(format!(
+                r#"<rect x="{x:.1}" y="{}" width="{bar_width:.1}" height="{}" fill="{color}"/>"#,
+                y + 2.0,
+                CHART_ROW_HEIGHT - 4.0
+            ));
+            x += bar_width;
+        }
+
+        lines.push(format!(
+            r#"<text x="{:.1}" y="{}">{:.2}ms</text>"#,
+            x + 4.0,
+            y + 13.0,
+            timing.total_nanos / 1_000_000_f64
+        ));
+    }
+
+    lines.push("</svg>".into());
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::time::Duration;
+
+    use super::{construct_chart, relative_bar, update_content, MARKER};
+    use crate::template::config::ReportOptions;
+    use crate::template::runner::BenchStats;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+    use crate::{day, year};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(10))),
+                    part_2: Some(PartTiming::Legacy(Duration::from_millis(20))),
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    total_nanos: 3e+7,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(30))),
+                    part_2: Some(PartTiming::Legacy(Duration::from_millis(40))),
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    total_nanos: 7e+7,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(40))),
+                    part_2: Some(PartTiming::Legacy(Duration::from_millis(50))),
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    total_nanos: 9e+7,
+                    environment: None,
                 },
             ],
         }
@@ -131,47 +325,92 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::plain(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::plain(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::plain(),
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2024 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::plain(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::plain(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2024 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::plain(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## 2024 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +419,61 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_rich_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1 = Some(PartTiming::Benched(BenchStats {
+            samples: 100,
+            ..BenchStats::single(Duration::from_millis(10))
+        }));
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let options = ReportOptions {
+            ranking: 2,
+            ..ReportOptions::default()
+        };
+        update_content(&mut s, year!(2024), timings, 190.0, &options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## 2024 Benchmarks",
+            "",
+            "![2024 benchmarks](./benchmarks-2024.svg)",
+            "",
+            "| Day | Part 1 | Part 2 | Samples | Relative cost | Puzzle |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` | 100 / - | `███░░░░░░░` | [adventofcode.com](https://adventofcode.com/2024/day/1) |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` | - / - | `████████░░` | [adventofcode.com](https://adventofcode.com/2024/day/2) |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` | - / - | `██████████` | [adventofcode.com](https://adventofcode.com/2024/day/4) |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "**Slowest days:**",
+            "",
+            "1. Day 4: `90.00ms` (47.4% of total)",
+            "2. Day 2: `70.00ms` (36.8% of total)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn fills_relative_bars() {
+        assert_eq!(relative_bar(0.0), "█░░░░░░░░░");
+        assert_eq!(relative_bar(0.54), "█████░░░░░");
+        assert_eq!(relative_bar(1.0), "██████████");
+    }
+
+    #[test]
+    fn constructs_chart() {
+        let chart = construct_chart(year!(2024), &get_mock_timings());
+        assert!(chart.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(chart.contains("<title>2024 Benchmarks</title>"));
+        assert_eq!(chart.matches(">Day ").count(), 3);
+        // the parts of the slowest day span the full width of the bars.
+        assert!(chart
+            .contains(r##"<rect x="247.8" y="72" width="222.2" height="16" fill="#f5a623"/>"##));
+        assert!(chart.ends_with("</svg>\n"));
+    }
 }
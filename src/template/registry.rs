@@ -0,0 +1,598 @@
+//! In-process registry of solutions.
+//! The `solution!` macro creates one [`Solution`] per day and `build.rs` collects them into the `all` binary,
+//! which runs every day in a single process and reports [`Results`] instead of printed text.
+
+use rayon::prelude::*;
+use std::{collections::HashMap, collections::HashSet, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::provider::{FileCache, ProviderError, Resource};
+use crate::template::runner::{
+    measure, print_part, print_result, read_answers, BenchStats, HeapSummary, PartFailure,
+};
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{all_days, Day, OutputFormat, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
+
+/// A type-erased solution part, returning the answer formatted as a string.
+pub type PartFn = fn(&str) -> Option<String>;
+
+/// The parts of a single day, as registered by the `solution!` macro.
+pub struct Solution {
+    pub puzzle: Puzzle,
+    pub parts: &'static [(u8, PartFn)],
+}
+
+/// Outcome of running a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The accepted answer from `answers.json`, if known.
+    pub expected: Option<String>,
+    pub stats: BenchStats,
+    /// Allocations of the part, if it was profiled with dhat.
+    pub heap: Option<HeapSummary>,
+    /// Why the part did not produce an answer, if it timed out or panicked.
+    pub failure: Option<PartFailure>,
+}
+
+/// Outcome of running all parts of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolutionResult {
+    pub puzzle: Puzzle,
+    pub parts: Vec<PartResult>,
+}
+
+/// Outcomes of a run over several days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Results {
+    pub data: Vec<SolutionResult>,
+}
+
+impl Solution {
+    /// Runs every part on `input`, printing results as text as they come in if `print` is set.
+    pub fn run(
+        &self,
+        input: &'static str,
+        answers: &Answers,
+        is_timed: bool,
+        print: bool,
+    ) -> SolutionResult {
+        let parts = self
+            .parts
+            .iter()
+            .map(|&(part, func)| {
+                let expected = answers.get(self.puzzle.day, part).map(str::to_string);
+                run_part(func, input, part, expected, is_timed, print)
+            })
+            .collect();
+
+        SolutionResult {
+            puzzle: self.puzzle,
+            parts,
+        }
+    }
+}
+
+fn run_part(
+    func: PartFn,
+    input: &'static str,
+    part: u8,
+    expected: Option<String>,
+    is_timed: bool,
+    print: bool,
+) -> PartResult {
+    let part_str = format!("Part {part}");
+
+    let (answer, stats, heap, failure) = match measure(func, input, is_timed, print, |answer| {
+        if print {
+            print_result(answer, &part_str, "");
+        }
+    }) {
+        Ok((answer, stats, heap)) => (answer, stats, heap, None),
+        Err(failure) => (None, BenchStats::default(), None, Some(failure)),
+    };
+
+    let result = PartResult {
+        part,
+        answer,
+        expected,
+        stats,
+        heap,
+        failure,
+    };
+
+    if print {
+        print_part(&result);
+    }
+
+    result
+}
+
+/// Outcome of running a single day.
+enum DayOutcome {
+    NotSolved,
+    MissingInput(ProviderError),
+    Solved(SolutionResult),
+}
+
+/// The registered solution of a day and its input, if the day is solved.
+type DayInput<'a> = Option<(&'a Solution, Result<String, ProviderError>)>;
+
+/// Looks up the solution of every day and reads its input through the [`FileCache`], which
+/// verifies it against its recorded checksum.
+/// NOTE: read serially before running any day, the cache records checksums of new inputs.
+fn read_day_inputs<'a>(solutions: &'a [Solution], year: Year, days: &[Day]) -> Vec<DayInput<'a>> {
+    let cache = FileCache::from_env();
+
+    days.iter()
+        .map(|&day| {
+            let puzzle = Puzzle::new(year, day);
+            let solution = solutions.iter().find(|s| s.puzzle == puzzle)?;
+            Some((solution, cache.get(puzzle, Resource::Input)))
+        })
+        .collect()
+}
+
+fn run_day(day_input: DayInput, answers: &Answers, is_timed: bool, print: bool) -> DayOutcome {
+    match day_input {
+        None => DayOutcome::NotSolved,
+        // NOTE: leaked, parts are executed on their own thread (see `runner::run_isolated`).
+        Some((solution, Ok(input))) => {
+            DayOutcome::Solved(solution.run(input.leak(), answers, is_timed, print))
+        }
+        Some((_, Err(e))) => DayOutcome::MissingInput(e),
+    }
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn print_day_outcome(day: Day, outcome: &DayOutcome, format: OutputFormat) {
+    match outcome {
+        DayOutcome::NotSolved if format.is_text() => println!("Not solved."),
+        DayOutcome::MissingInput(e) => eprintln!("Could not read input of day {day}: {e}"),
+        _ => {}
+    }
+}
+
+/// Runs the registered solutions of `year` for the given days, in order.
+/// Days without a registered solution are reported as not solved, days without an input file are skipped.
+///
+/// Untimed runs execute up to `jobs` days concurrently (`0` uses all cores). Their output is printed
+/// per day once all of them are done. Timed runs are always serial so benchmarks don't interfere.
+pub fn run_solutions(
+    solutions: &[Solution],
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_timed: bool,
+    jobs: usize,
+    format: OutputFormat,
+) -> Results {
+    let answers = read_answers(year);
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+    let day_inputs = read_day_inputs(solutions, year, &days);
+
+    let outcomes: Vec<(Day, DayOutcome)> = if is_timed || jobs == 1 {
+        days.into_iter()
+            .zip(day_inputs)
+            .enumerate()
+            .map(|(i, (day, day_input))| {
+                if format.is_text() {
+                    print_day_header(day, i > 0);
+                }
+                let outcome = run_day(day_input, &answers, is_timed, format.is_text());
+                print_day_outcome(day, &outcome, format);
+                (day, outcome)
+            })
+            .collect()
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("failed to start thread pool");
+
+        // parts are run without printing, output is grouped per day afterwards.
+        let outcomes: Vec<_> = pool.install(|| {
+            days.into_par_iter()
+                .zip(day_inputs)
+                .map(|(day, day_input)| (day, run_day(day_input, &answers, false, false)))
+                .collect()
+        });
+
+        for (i, (day, outcome)) in outcomes.iter().enumerate() {
+            if format.is_text() {
+                print_day_header(*day, i > 0);
+                if let DayOutcome::Solved(result) = outcome {
+                    result.parts.iter().for_each(print_part);
+                }
+            }
+            print_day_outcome(*day, outcome, format);
+        }
+
+        outcomes
+    };
+
+    Results {
+        data: outcomes
+            .into_iter()
+            .filter_map(|(_, outcome)| match outcome {
+                DayOutcome::Solved(result) => Some(result),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Results {
+    /// Whether any part did not reproduce its accepted answer.
+    pub fn has_regressions(&self) -> bool {
+        self.data.iter().any(SolutionResult::has_regressions)
+    }
+
+    /// Dehydrate results to a JSON file.
+    pub fn store_file(&self, path: &str) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate results from a JSON file.
+    pub fn read_from_file(path: &str) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|x| x.to_string())
+            .and_then(Results::try_from)
+    }
+}
+
+impl SolutionResult {
+    /// Whether any part did not reproduce its accepted answer.
+    pub fn has_regressions(&self) -> bool {
+        self.parts.iter().any(PartResult::is_regression)
+    }
+}
+
+impl PartResult {
+    /// Whether the part has an accepted answer and did not reproduce it.
+    pub fn is_regression(&self) -> bool {
+        self.expected.is_some() && self.answer != self.expected
+    }
+
+    /// Summarizes the outcome, e.g. for machine readable output.
+    pub fn status(&self) -> &'static str {
+        if let Some(failure) = &self.failure {
+            match failure {
+                PartFailure::Timeout => "timeout",
+                PartFailure::Panicked(_) => "panicked",
+            }
+        } else if self.is_regression() {
+            "wrong"
+        } else if self.answer.is_none() {
+            "unsolved"
+        } else if self.expected.is_some() {
+            "correct"
+        } else {
+            "solved"
+        }
+    }
+}
+
+impl From<&SolutionResult> for Timing {
+    fn from(value: &SolutionResult) -> Self {
+        let mut timing = Timing {
+            day: value.puzzle.day,
+            part_1: None,
+            part_2: None,
+            part_1_heap: None,
+            part_2_heap: None,
+            total_nanos: 0_f64,
+            environment: None,
+        };
+
+        for part in value.parts.iter().filter(|p| p.answer.is_some()) {
+            let benched = Some(PartTiming::Benched(part.stats));
+            match part.part {
+                1 => (timing.part_1, timing.part_1_heap) = (benched, part.heap),
+                2 => (timing.part_2, timing.part_2_heap) = (benched, part.heap),
+                _ => continue,
+            }
+            timing.total_nanos += part.stats.mean.as_nanos() as f64;
+        }
+
+        timing
+    }
+}
+
+impl From<&Results> for Timings {
+    fn from(value: &Results) -> Self {
+        Timings {
+            data: value.data.iter().map(Timing::from).collect(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Results> for JsonValue {
+    fn from(value: &Results) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Results {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Results {
+            data: json_data
+                .iter()
+                .map(SolutionResult::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&SolutionResult> for JsonValue {
+    fn from(value: &SolutionResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert(
+            "parts".into(),
+            JsonValue::Array(value.parts.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for SolutionResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected result.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected result.day to be a Day struct.")?;
+
+        let parts = json
+            .get("parts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected result.parts to be an array.")?
+            .iter()
+            .map(PartResult::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(SolutionResult {
+            puzzle: Puzzle::new(year, day),
+            parts,
+        })
+    }
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "expected".into(),
+            match &value.expected {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.stats.mean.as_nanos() as f64),
+        );
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.samples as f64),
+        );
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "failure".into(),
+            match &value.failure {
+                Some(x) => JsonValue::String(x.to_string()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("status".into(), JsonValue::String(value.status().into()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected part.answer to be null or string.")?;
+
+        let expected = json
+            .get("expected")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected part.expected to be null or string.")?;
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected part.stats to be present.")?;
+        let stats = BenchStats::try_from(stats)?;
+
+        let heap = match json.get("heap") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(HeapSummary::try_from(v)?),
+        };
+
+        let failure = match json.get("failure") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(s)) => Some(s.parse()?),
+            Some(_) => return Err("Expected part.failure to be null or string.".into()),
+        };
+
+        Ok(PartResult {
+            part: part as u8,
+            answer: answer.cloned(),
+            expected: expected.cloned(),
+            stats,
+            heap,
+            failure,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{PartResult, Results, SolutionResult};
+    use crate::template::alloc_counter::AllocCount;
+    use crate::template::runner::{BenchStats, HeapSummary, PartFailure};
+    use crate::template::{timings::Timings, Puzzle};
+    use crate::{day, year};
+
+    fn get_mock_results() -> Results {
+        Results {
+            data: vec![SolutionResult {
+                puzzle: Puzzle::new(year!(2024), day!(1)),
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Some("11".into()),
+                        expected: Some("11".into()),
+                        stats: BenchStats {
+                            samples: 100,
+                            allocations: Some(AllocCount {
+                                allocations: 3,
+                                bytes: 96,
+                            }),
+                            ..BenchStats::single(Duration::from_micros(20))
+                        },
+                        heap: Some(HeapSummary {
+                            total_bytes: 4096,
+                            peak_bytes: 1024,
+                            allocations: 12,
+                        }),
+                        failure: None,
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: None,
+                        expected: None,
+                        stats: BenchStats {
+                            samples: 100,
+                            ..BenchStats::single(Duration::from_micros(5))
+                        },
+                        heap: None,
+                        failure: Some(PartFailure::Panicked("oops".into())),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let results = get_mock_results();
+        let json = tinyjson::JsonValue::from(&results).stringify().unwrap();
+        assert_eq!(Results::try_from(json).unwrap(), results);
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let mut results = get_mock_results();
+        assert!(!results.has_regressions());
+        assert_eq!(results.data[0].parts[0].status(), "correct");
+        assert_eq!(results.data[0].parts[1].status(), "panicked");
+
+        results.data[0].parts[0].answer = Some("12".into());
+        assert!(results.has_regressions());
+        assert_eq!(results.data[0].parts[0].status(), "wrong");
+    }
+
+    #[test]
+    fn converts_to_timings() {
+        let timings = Timings::from(&get_mock_results());
+        let timing = timings.data.first().unwrap();
+        assert_eq!(timing.day, day!(1));
+        assert_eq!(timing.part_duration(1), Some(Duration::from_micros(20)));
+        assert_eq!(timing.part_1.map(|t| t.samples()), Some(100));
+        assert_eq!(timing.part_2, None);
+        assert_eq!(timing.part_1_heap.map(|h| h.peak_bytes), Some(1024));
+        assert_eq!(timing.total_nanos, 20_000_f64);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
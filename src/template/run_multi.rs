@@ -1,55 +1,93 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
-
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+use crate::template::{Day, OutputFormat, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+use super::{registry::Results, timings::Timings};
+
+/// How the `all` binary is built for a run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Build {
+    Debug,
+    Release,
+    /// Release build that counts the allocations of benched parts, see `alloc_counter`.
+    CountAllocs,
+    /// Profiles the heap of each part with dhat, see `runner::HeapSummary`.
+    Dhat,
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+impl Build {
+    /// Name of the cargo profile the binary is built with.
+    pub fn profile(self) -> &'static str {
+        match self {
+            Build::Debug => "dev",
+            Build::Release | Build::CountAllocs => "release",
+            Build::Dhat => "dhat",
+        }
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    /// Cargo features the binary is built with.
+    pub fn features(self) -> &'static [&'static str] {
+        match self {
+            Build::Debug | Build::Release => &[],
+            Build::CountAllocs => &["count-allocs"],
+            Build::Dhat => &["dhat-heap"],
+        }
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    fn cargo_args(self) -> Vec<String> {
+        let mut args = match self {
+            Build::Debug => vec![],
+            profile => vec!["--profile".to_string(), profile.profile().to_string()],
+        };
+        if !self.features().is_empty() {
+            args.push("--features".into());
+            args.push(self.features().join(","));
+        }
+        args
+    }
+}
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    build: Build,
+    is_timed: bool,
+    jobs: usize,
+    format: OutputFormat,
+) -> Result<Results, Error> {
+    let results =
+        child_commands::run_solutions(year, days_to_run, is_timed, build, jobs, format, false)?;
+
+    if is_timed && format.is_text() {
+        let total_millis = Timings::from(&results).total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+    }
+
+    if format.is_text() {
+        print_regressions(&results);
+    }
+
+    Ok(results)
+}
+
+fn print_regressions(results: &Results) {
+    for result in results.data.iter().filter(|r| r.has_regressions()) {
+        for part in result.parts.iter().filter(|p| p.is_regression()) {
+            eprintln!(
+                "Regression: day {} part {} no longer produces its accepted answer.",
+                result.puzzle.day, part.part
+            );
+        }
     }
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
+    BadExitStatus,
+    Results(String),
     IO(io::Error),
 }
 
@@ -59,199 +97,99 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions are compiled into the `all` binary, which runs them in-process.
+/// This module encapsulates invoking that binary and reading back its results.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Build, Error, Results};
+    use crate::template::{cli::AllArgs, Day, OutputFormat, Year};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        collections::HashSet,
+        env, fs,
+        io::{self, Write},
+        process::{self, Command, Stdio},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
-
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    /// Run the solutions of the given days and collect their results.
+    /// Untimed runs execute up to `jobs` days concurrently.
+    /// A `quiet` run only prints the output of the child if it fails, e.g. because it doesn't build.
+    pub fn run_solutions(
+        year: Year,
+        days: &HashSet<Day>,
+        is_timed: bool,
+        build: Build,
+        jobs: usize,
+        format: OutputFormat,
+        quiet: bool,
+    ) -> Result<Results, Error> {
+        // an empty selection can't be passed on to the child, there is nothing to run anyway.
+        if days.is_empty() {
+            return Ok(Results { data: vec![] });
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
-    }
-
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+        let results_path = env::temp_dir()
+            .join(format!("aoc-results-{}.json", process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        let child_args = AllArgs {
+            year,
+            days: Some(days.iter().copied().collect()),
+            results: Some(results_path.clone()),
+            format,
+            jobs,
+            time: is_timed,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+        let mut args: Vec<String> =
+            vec!["run".into(), "--quiet".into(), "--bin".into(), "all".into()];
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        args.extend(build.cargo_args());
+        args.push("--".into());
+        args.extend(child_args.to_args());
 
-        Some((str_timing, parsed_timing))
-    }
+        // output of the child is forwarded as is, results are exchanged via a file.
+        let mut command = cargo();
+        command.args(&args);
+        let status = if quiet {
+            let output = command.stdin(Stdio::null()).output()?;
+            if !output.status.success() {
+                io::stderr().write_all(&output.stderr)?;
+            }
+            output.status
+        } else {
+            command
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .status()?
+        };
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+        // NOTE: the child exits with a non-zero status on regressions, but still reports results.
+        let results = Results::read_from_file(&results_path).map_err(|e| {
+            if status.success() {
+                Error::Results(e)
+            } else {
+                Error::BadExitStatus
+            }
+        });
+        let _ = fs::remove_file(&results_path);
+        results
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+    /// A `cargo` command without the build variables the outer `cargo run` sets for this process.
+    /// Build scripts such as ring's watch them, passing them on would rebuild them on every run.
+    pub fn cargo() -> Command {
+        let mut cmd = Command::new("cargo");
+        for (key, _) in env::vars_os() {
+            let key = key.to_string_lossy();
+            if key.starts_with("CARGO_PKG_")
+                || key.starts_with("CARGO_MANIFEST_")
+                || key == "CARGO_CRATE_NAME"
+                || key == "CARGO_BIN_NAME"
+                || key == "OUT_DIR"
+            {
+                cmd.env_remove(key.as_ref());
+            }
         }
+        cmd
     }
 }
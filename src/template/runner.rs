@@ -1,96 +1,541 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
+use std::io::{self, stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+#[cfg(feature = "dhat-heap")]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, fs, process, str::FromStr};
+use tinyjson::JsonValue;
 
+use crate::template::alloc_counter::{self, AllocCount};
+use crate::template::answers::Answers;
+use crate::template::cli::SolutionArgs;
+use crate::template::params::Parameters;
+use crate::template::provider::{FileCache, ProviderError, Resource};
+use crate::template::registry::{PartResult, SolutionResult};
+use crate::template::submissions::{Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, config::Config, OutputFormat, Puzzle, Year, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Stack size of the thread a part is first executed on, solutions may recurse deeply.
+const PART_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+pub fn run_part<I, T, F>(
+    func: F,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    args: &SolutionArgs,
+) -> PartResult
+where
+    I: Copy + Send + 'static,
+    T: Display + Send + 'static,
+    F: Fn(I) -> Option<T> + Copy + Send + 'static,
+{
     let part_str = format!("Part {part}");
+    let format = args.format;
+
+    let (answer, stats, heap, failure) =
+        match measure(func, input, args.time, format.is_text(), |result| {
+            if format.is_text() {
+                print_result(result, &part_str, "");
+            }
+        }) {
+            Ok((result, stats, heap)) => {
+                (result.map(|result| result.to_string()), stats, heap, None)
+            }
+            Err(failure) => (None, BenchStats::default(), None, Some(failure)),
+        };
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let part_result = PartResult {
+        part,
+        answer,
+        // accepted answers only apply to the downloaded input and default parameters.
+        expected: if args.input.is_some() || !args.params.is_empty() {
+            None
+        } else {
+            read_answers(puzzle.year)
+                .get(puzzle.day, part)
+                .map(str::to_string)
+        },
+        stats,
+        heap,
+        failure,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if format.is_text() {
+        print_part(&part_result);
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(answer) = &part_result.answer {
+        if args.submit == Some(part) {
+            submit_result(answer, puzzle, part);
+        }
     }
+
+    part_result
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
+/// Reads the input of a solution binary: the cached input by default, which is downloaded if it's
+/// missing, otherwise the file at `path` or stdin if `path` is `-`.
+pub fn read_input(puzzle: Puzzle, path: Option<&str>) -> String {
+    let result = match path {
+        None => FileCache::from_env().get(puzzle, Resource::Input),
+        Some("-") => io::read_to_string(io::stdin()).map_err(ProviderError::from),
+        Some(path) => fs::read_to_string(path).map_err(ProviderError::from),
+    };
+
+    result.unwrap_or_else(|e| {
+        eprintln!("Failed to read input: {e}");
+        process::exit(1);
+    })
+}
+
+/// Reads the accepted answers of a year. A malformed answers file is an error rather than
+/// silently disabling the regression check.
+pub fn read_answers(year: Year) -> Answers {
+    Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read accepted answers {e}");
+        process::exit(1);
+    })
+}
+
+/// Reads the parameters of a solution binary: the defaults with the `--param` overrides applied.
+pub fn read_params<P: Parameters>(args: &SolutionArgs) -> P {
+    let overrides = args.params.iter().map(|(n, v)| (n.as_str(), v.as_str()));
+    P::with_overrides(overrides).unwrap_or_else(|e| {
+        eprintln!("Failed to set parameters: {e}");
+        process::exit(1);
+    })
+}
+
+/// Finishes a run of a solution binary: prints the results if requested in a machine readable format
+/// and exits with a non-zero status if any part no longer produces its accepted answer.
+pub fn report_results(puzzle: Puzzle, parts: Vec<PartResult>, args: &SolutionArgs) {
+    let result = SolutionResult { puzzle, parts };
+
+    if args.format == OutputFormat::Json {
+        println!("{}", JsonValue::from(&result).stringify().unwrap());
+    }
+
+    if result.has_regressions() {
+        process::exit(1);
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether we are running a timed run or not:
+///  1. by default, the function is executed once.
+///  2. when timed, the function is benched (see [`bench()`]).
+///
+/// The first execution is isolated (see [`run_isolated`]), a part that panics or exceeds the
+/// configured timeout is reported as a [`PartFailure`] and not benched. When built with `dhat-heap`,
+/// the allocations of the first execution are summarized as well. Benching progress is only printed
+/// if `print` is set.
+pub fn measure<I, T, F>(
+    func: F,
     input: I,
+    is_timed: bool,
+    print: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
-    let base_time = timer.elapsed();
+) -> Result<(T, BenchStats, Option<HeapSummary>), PartFailure>
+where
+    I: Copy + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Copy + Send + 'static,
+{
+    let config = Config::from_env();
+    let (result, base_time, heap) = run_isolated(func, input, config.part_timeout)?;
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if is_timed {
+        warn_about_running_parts();
+        // NOTE: benched on its own thread as well, every run may take as long as the first one may.
+        let runs = config.bench_warmup + bench_iterations(&config, &base_time) as u32;
+        let timeout = config.part_timeout.map(|t| t.saturating_mul(runs));
+        let (stats, _, _) = run_isolated(
+            move |input| bench(func, input, &base_time, print),
+            input,
+            timeout,
+        )?;
+        stats
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
+    };
+
+    Ok((result, stats, heap))
+}
+
+/// Number of parts whose thread is still running, e.g. because they timed out.
+static RUNNING_PARTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Parts that timed out keep running in the background and compete with the benched part for the CPU.
+fn warn_about_running_parts() {
+    static WARNING: Once = Once::new();
+
+    let running = RUNNING_PARTS.load(Ordering::SeqCst);
+    if running > 0 {
+        WARNING.call_once(|| {
+            eprintln!(
+                "Warning: {running} timed out part(s) still running in the background, benchmarks may be skewed."
+            );
+        });
+    }
+}
+
+/// Execute a part once on its own thread, so a panic or an endless loop doesn't take down the runner.
+/// On timeout, the thread is left running in the background until the process exits.
+/// When built with `dhat-heap`, the execution is profiled and its allocations are summarized.
+pub fn run_isolated<I, T, F>(
+    func: F,
+    input: I,
+    timeout: Option<Duration>,
+) -> Result<(T, Duration, Option<HeapSummary>), PartFailure>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(I) -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    RUNNING_PARTS.fetch_add(1, Ordering::SeqCst);
+    thread::Builder::new()
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let timer = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| profile(|| func(input))));
+            let elapsed = timer.elapsed();
+            RUNNING_PARTS.fetch_sub(1, Ordering::SeqCst);
+            // NOTE: the receiver is gone if the part timed out.
+            let _ = sender.send(result.map(|(result, heap)| (result, elapsed, heap)));
+        })
+        .expect("failed to spawn thread for solution part");
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(RecvTimeoutError::from),
     };
 
-    (result, run.0, run.1)
+    match received {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(payload)) => Err(PartFailure::Panicked(panic_message(payload.as_ref()))),
+        Err(RecvTimeoutError::Timeout) => Err(PartFailure::Timeout),
+        Err(RecvTimeoutError::Disconnected) => Err(PartFailure::Panicked("thread exited".into())),
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Whether a dhat profiler is running, only one can run per process.
+#[cfg(feature = "dhat-heap")]
+static PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// Executes `func` with a dhat profiler and summarizes its allocations.
+/// A part that timed out keeps its profiler until it finishes, later parts aren't profiled meanwhile.
+#[cfg(feature = "dhat-heap")]
+fn profile<T>(func: impl FnOnce() -> T) -> (T, Option<HeapSummary>) {
+    /// Marks the profiler as stopped once dropped, also if the part panics.
+    struct Stopped;
+
+    impl Drop for Stopped {
+        fn drop(&mut self) {
+            PROFILING.store(false, Ordering::SeqCst);
+        }
+    }
+
+    if PROFILING.swap(true, Ordering::SeqCst) {
+        eprintln!("Warning: not profiling the heap, a timed out part is still being profiled.");
+        return (func(), None);
+    }
+
+    // NOTE: declared first, so it's dropped after the profiler.
+    let _stopped = Stopped;
+    let _profiler = dhat::Profiler::new_heap();
+    let result = func();
+    (result, HeapSummary::current())
+}
+
+/// Allocations are only profiled when built with `dhat-heap`.
+#[cfg(not(feature = "dhat-heap"))]
+fn profile<T>(func: impl FnOnce() -> T) -> (T, Option<HeapSummary>) {
+    (func(), None)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".into())
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Why a part did not produce a result.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PartFailure {
+    /// The part did not finish within the configured timeout.
+    Timeout,
+    /// The part panicked, with the panic message.
+    Panicked(String),
+}
+
+impl Display for PartFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartFailure::Timeout => f.write_str("timeout"),
+            PartFailure::Panicked(msg) => write!(f, "panicked: {msg}"),
+        }
+    }
+}
+
+impl FromStr for PartFailure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timeout" => Ok(PartFailure::Timeout),
+            _ => s
+                .strip_prefix("panicked: ")
+                .map(|msg| PartFailure::Panicked(msg.into()))
+                .ok_or_else(|| format!("unknown part failure `{s}`")),
+        }
+    }
+}
+
+/// Bench a solution part: after a few warm-up iterations, the function is executed for approx. the
+/// configured time budget (1 second by default), but at least 10 and at most 10000 times.
+/// When built with `count-allocs`, the allocations of each iteration are counted as well.
+/// Progress is only printed if `print` is set.
+pub fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    print: bool,
+) -> BenchStats {
+    if print {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let config = Config::from_env();
+
+    for _ in 0..config.bench_warmup {
+        black_box(func(black_box(input)));
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = bench_iterations(&config, base_time);
 
-    let mut timers: Vec<Duration> = vec![];
+    // NOTE: allocated up front, growing it in the loop would be counted against the part.
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
+    let mut allocations = AllocCount::default();
 
     for _ in 0..bench_iterations {
+        let start = AllocCount::now();
         let timer = Instant::now();
         black_box(func(black_box(input)));
-        timers.push(timer.elapsed());
+        let elapsed = timer.elapsed();
+        allocations += AllocCount::since(start);
+        timers.push(elapsed);
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats {
+        allocations: alloc_counter::is_enabled()
+            .then(|| allocations.per_run(bench_iterations as u64)),
+        ..BenchStats::from_samples(timers)
+    }
+}
+
+/// Number of timed runs of a part whose first run took `base_time`, fitting the bench budget.
+fn bench_iterations(config: &Config, base_time: &Duration) -> u128 {
+    (config.bench_budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Summary statistics of the samples of a benchmark.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Number of samples the statistics are based on.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+    /// Average allocations of a run, if built with `count-allocs`.
+    /// Counted process-wide, so this includes allocations of other threads running meanwhile.
+    pub allocations: Option<AllocCount>,
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+impl BenchStats {
+    /// Statistics of a single, untimed execution.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            mean: duration,
+            min: duration,
+            median: duration,
+            p95: duration,
+            stddev: Duration::ZERO,
+            samples: 1,
+            outliers: 0,
+            allocations: None,
+        }
+    }
+
+    /// Computes statistics after rejecting outliers outside of Tukey's fences (1.5 IQR beyond the quartiles).
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+
+        let iqr = percentile(&samples, 75) - percentile(&samples, 25);
+        let lower = percentile(&samples, 25).saturating_sub(iqr * 3 / 2);
+        let upper = percentile(&samples, 75) + iqr * 3 / 2;
+
+        let total = samples.len();
+        samples.retain(|d| (lower..=upper).contains(d));
+
+        let count = samples.len() as u128;
+        let mean_nanos = samples.iter().map(Duration::as_nanos).sum::<u128>() / count;
+        let variance = samples
+            .iter()
+            .map(|d| (d.as_nanos() as f64 - mean_nanos as f64).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Self {
+            #[allow(clippy::cast_possible_truncation)]
+            mean: Duration::from_nanos(mean_nanos as u64),
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+            samples: count,
+            outliers: (total - samples.len()) as u128,
+            allocations: None,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    sorted[(sorted.len() - 1) * p / 100]
+}
+
+/// Summary of the heap allocations of a part, as recorded by dhat.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HeapSummary {
+    /// Bytes allocated over the entire execution.
+    pub total_bytes: u64,
+    /// Bytes allocated at the same time at the peak of the execution.
+    pub peak_bytes: u64,
+    /// Number of allocations over the entire execution.
+    pub allocations: u64,
+}
+
+impl HeapSummary {
+    /// Allocations since the running dhat profiler was started.
+    #[cfg(feature = "dhat-heap")]
+    pub fn current() -> Option<Self> {
+        let stats = dhat::HeapStats::get();
+        Some(Self {
+            total_bytes: stats.total_bytes,
+            peak_bytes: stats.max_bytes as u64,
+            allocations: stats.total_blocks,
+        })
+    }
+}
+
+impl Display for HeapSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocated, {} peak, {} allocations",
+            format_bytes(self.total_bytes),
+            format_bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", units[unit])
+    }
+}
+
+pub fn format_stats(stats: &BenchStats) -> String {
+    let BenchStats {
+        mean,
+        median,
+        p95,
+        stddev,
+        samples,
+        outliers,
+        ..
+    } = stats;
+
+    if *samples == 1 {
+        format!(" ({mean:.1?})")
+    } else if *outliers == 0 {
+        format!(
+            " ({mean:.1?} @ {samples} samples, median {median:.1?}, p95 {p95:.1?}, σ {stddev:.1?})"
+        )
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({mean:.1?} @ {samples} samples, median {median:.1?}, p95 {p95:.1?}, σ {stddev:.1?}, {outliers} outliers)")
+    }
+}
+
+/// Marks a result as matching (✔) or not matching (✘) its accepted answer, if there is one.
+pub fn format_verdict(result: &PartResult) -> String {
+    match &result.expected {
+        None => String::new(),
+        Some(_) if !result.is_regression() => " ✔".into(),
+        Some(expected) => format!(" ✘ (expected {expected})"),
+    }
+}
+
+/// Print the final line of a part: its answer, failure or missing answer, followed by stats and verdict.
+pub fn print_part(result: &PartResult) {
+    let part_str = format!("Part {}", result.part);
+
+    match &result.failure {
+        Some(failure) => {
+            print!("\r");
+            println!("{part_str}: {failure}{}", format_verdict(result));
+        }
+        None => {
+            let mut duration_str = format_stats(&result.stats);
+            if let Some(allocations) = &result.stats.allocations {
+                duration_str += &format!(" [{allocations} per run]");
+            }
+            if let Some(heap) = &result.heap {
+                duration_str += &format!(" [{heap}]");
+            }
+            duration_str += &format_verdict(result);
+            print_result(&result.answer, &part_str, &duration_str);
+        }
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -125,41 +570,125 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
+/// Submit one part of the solution, as requested with `--submit`, if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the answer is not known to be wrong from earlier submissions (see [`Submissions::check`]).
+///
+/// The verdict is recorded in the submission ledger of the year.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: Puzzle,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    let answer = result.to_string();
+    let mut submissions = match Submissions::read_from_file(puzzle.year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting {answer}: failed to read the submission ledger {e}");
+            return None;
+        }
+    };
 
-    if !args.contains(&"--submit".into()) {
+    if let Err(refusal) = submissions.check(puzzle.day, part, &answer) {
+        eprintln!("Not submitting {answer}: {refusal}");
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result via aoc-cli...");
+    let output = aoc_cli::submit(puzzle, part, &answer);
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    if let Ok(output) = &output {
+        let verdict = Verdict::from_response(&String::from_utf8_lossy(&output.stdout));
+        submissions.record(puzzle.day, part, &answer, verdict);
+        if let Err(e) = submissions.store_file(puzzle.year) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
 
-    if part_submit != part {
-        return None;
+    Some(output)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::{thread, time::Duration};
+
+    use super::{measure, run_isolated, BenchStats, HeapSummary, PartFailure};
+
+    #[test]
+    fn computes_stats() {
+        let samples = (1..=10).map(Duration::from_micros).collect();
+        let stats = BenchStats::from_samples(samples);
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.mean, Duration::from_nanos(5500));
+        assert_eq!(stats.median, Duration::from_micros(5));
+        assert_eq!(stats.p95, Duration::from_micros(9));
+        assert_eq!(stats.stddev, Duration::from_nanos(2872));
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn rejects_outliers() {
+        let mut samples: Vec<Duration> = (0..20).map(|_| Duration::from_micros(10)).collect();
+        samples.push(Duration::from_millis(5));
+        let stats = BenchStats::from_samples(samples);
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, Duration::from_micros(10));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn formats_heap_summaries() {
+        let heap = HeapSummary {
+            total_bytes: 3 * 1024 * 1024 / 2,
+            peak_bytes: 512,
+            allocations: 7,
+        };
+        assert_eq!(
+            heap.to_string(),
+            "1.5 MiB allocated, 512 B peak, 7 allocations"
+        );
+    }
+
+    #[test]
+    fn isolates_parts() {
+        let result = run_isolated(|x: u32| x * 2, 21, None);
+        assert_eq!(result.map(|(answer, _, _)| answer), Ok(42));
+
+        let result = run_isolated(|_: u32| -> u32 { panic!("bad input") }, 21, None);
+        assert_eq!(result, Err(PartFailure::Panicked("bad input".into())));
+
+        let sleep = |_: u32| thread::sleep(Duration::from_secs(5));
+        let result = run_isolated(sleep, 21, Some(Duration::from_millis(10)));
+        assert_eq!(result, Err(PartFailure::Timeout));
+    }
+
+    #[test]
+    fn isolates_benchmarks() {
+        static RUNS: AtomicU32 = AtomicU32::new(0);
+
+        // passes its first run, panics while it's benched.
+        let flaky = |x: u32| {
+            if RUNS.fetch_add(1, Ordering::SeqCst) > 0 {
+                panic!("flaky");
+            }
+            x
+        };
+        let result = measure(flaky, 21, true, false, |_| {});
+        assert_eq!(result, Err(PartFailure::Panicked("flaky".into())));
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        for failure in [PartFailure::Timeout, PartFailure::Panicked("oops".into())] {
+            assert_eq!(failure.to_string().parse::<PartFailure>(), Ok(failure));
+        }
+    }
 }
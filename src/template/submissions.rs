@@ -0,0 +1,384 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+fn get_submissions_path(year: Year) -> String {
+    year.data_path("submissions.json")
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was not evaluated, e.g. because of a lockout after a wrong answer.
+    NotEvaluated,
+}
+
+impl Verdict {
+    /// Interprets the response text of the advent of code website.
+    pub fn from_response(response: &str) -> Self {
+        let response = response.to_lowercase();
+
+        if response.contains("that's the right answer") {
+            Verdict::Correct
+        } else if response.contains("too high") {
+            Verdict::TooHigh
+        } else if response.contains("too low") {
+            Verdict::TooLow
+        } else if response.contains("not the right answer") {
+            Verdict::Wrong
+        } else {
+            Verdict::NotEvaluated
+        }
+    }
+
+    /// Whether the answer was evaluated, i.e. submitting it again would return the same verdict.
+    pub fn is_final(self) -> bool {
+        self != Verdict::NotEvaluated
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::NotEvaluated => "not evaluated",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "not evaluated" => Ok(Verdict::NotEvaluated),
+            _ => Err(format!("unknown verdict `{s}`")),
+        }
+    }
+}
+
+/// A single submitted answer and its verdict.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Why an answer is not submitted.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    Duplicate(Verdict),
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "the part was already solved with {answer}.")
+            }
+            Refusal::Duplicate(verdict) => {
+                write!(f, "the answer was submitted before and was {verdict}.")
+            }
+            Refusal::TooHigh(bound) => write!(f, "{bound} was already too high."),
+            Refusal::TooLow(bound) => write!(f, "{bound} was already too low."),
+        }
+    }
+}
+
+/// Every answer submitted for a year, guards against submitting answers that are known to be wrong.
+/// Can be serialized to and deserialized from JSON:
+/// `{ "data": [{ "day": "01", "part": 1, "answer": "1234", "verdict": "too low", "timestamp": 1733400000 }] }`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Serialize submissions as JSON and write them to the ledger of the given year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_submissions_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from the ledger of the given year. If not present, returns an empty ledger.
+    /// A ledger that can't be read or parsed is an error, its guards would silently be lost otherwise.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_submissions_path(year);
+        match fs::read_to_string(&path) {
+            Ok(content) => Submissions::try_from(content).map_err(|e| format!("{path}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{path}: {e}")),
+        }
+    }
+
+    /// Checks an answer against previous submissions of the same part.
+    /// Bounds from "too high" and "too low" responses only apply to integer answers.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let previous: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part && s.verdict.is_final())
+            .collect();
+
+        if let Some(correct) = previous.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(duplicate) = previous.iter().find(|s| s.answer == answer) {
+            return Err(Refusal::Duplicate(duplicate.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bounds = |verdict: Verdict| {
+            previous
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, bound)) = bounds(Verdict::TooHigh).filter(|(b, _)| value >= *b).min() {
+            return Err(Refusal::TooHigh(bound.clone()));
+        }
+
+        if let Some((_, bound)) = bounds(Verdict::TooLow).filter(|(b, _)| value <= *b).max() {
+            return Err(Refusal::TooLow(bound.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Records the verdict of a submitted answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+        });
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+            timestamp: *timestamp as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Refusal, Submissions, Verdict};
+    use crate::day;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", Verdict::TooHigh);
+        submissions.record(day!(1), 1, "10", Verdict::TooLow);
+        submissions.record(day!(1), 1, "50", Verdict::Wrong);
+        submissions.record(day!(1), 1, "60", Verdict::NotEvaluated);
+        submissions.record(day!(2), 1, "abc", Verdict::Correct);
+        submissions
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "100"),
+            Err(Refusal::Duplicate(Verdict::TooHigh))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "50"),
+            Err(Refusal::Duplicate(Verdict::Wrong))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "150"),
+            Err(Refusal::TooHigh("100".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "5"),
+            Err(Refusal::TooLow("10".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(2), 1, "def"),
+            Err(Refusal::AlreadySolved("abc".into()))
+        );
+    }
+
+    #[test]
+    fn allows_plausible_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(1), 1, "60"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "100"), Ok(()));
+        assert_eq!(submissions.check(day!(3), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently."),
+            Verdict::NotEvaluated
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,17 +1,116 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc_counter::AllocCount;
+use crate::template::environment::Environment;
+use crate::template::runner::{BenchStats, HeapSummary};
+use crate::template::{Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn get_timings_path(year: Year) -> String {
+    year.data_path("timings.json")
+}
+
+/// Benchmark of a single part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartTiming {
+    Benched(BenchStats),
+    /// Mean duration of a timing stored by an earlier version, which didn't record statistics.
+    Legacy(Duration),
+}
+
+impl PartTiming {
+    /// Mean duration of the part.
+    pub fn duration(&self) -> Duration {
+        match self {
+            PartTiming::Benched(stats) => stats.mean,
+            PartTiming::Legacy(mean) => *mean,
+        }
+    }
+
+    /// Number of samples the mean is based on, `0` for legacy timings.
+    pub fn samples(&self) -> u64 {
+        match self {
+            PartTiming::Benched(stats) => stats.samples as u64,
+            PartTiming::Legacy(_) => 0,
+        }
+    }
+
+    pub fn stats(&self) -> Option<&BenchStats> {
+        match self {
+            PartTiming::Benched(stats) => Some(stats),
+            PartTiming::Legacy(_) => None,
+        }
+    }
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1?}", self.duration())
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// Allocations of the parts, if the day was profiled with `time --dhat`.
+    pub part_1_heap: Option<HeapSummary>,
+    pub part_2_heap: Option<HeapSummary>,
     pub total_nanos: f64,
+    /// Machine and build the day was benched on, if recorded.
+    pub environment: Option<Environment>,
+}
+
+impl Timing {
+    /// Mean duration of a part, if it was benched.
+    pub fn part_duration(&self, part: u8) -> Option<Duration> {
+        let timing = match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        };
+        timing.map(|t| t.duration())
+    }
+}
+
+/// Parses a duration in the debug format of [`Duration`], e.g. `74.13ms`, as stored by earlier versions.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (value, factor) = [("ns", 1_f64), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+        .iter()
+        .find_map(|&(suffix, factor)| Some((s.strip_suffix(suffix)?, factor)))?;
+    let nanos = value.parse::<f64>().ok()? * factor;
+    Some(Duration::from_nanos(nanos as u64))
+}
+
+/// Change of a part's benchmark time between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl PartDelta {
+    /// Relative change in percent, positive when the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        let before = self.before.as_nanos() as f64;
+        (self.after.as_nanos() as f64 - before) / before.max(1.0) * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,19 +121,23 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of the given year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_timings_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of the given year. If not present, returns empty timings.
+    /// Files stored by earlier versions, which held durations as display strings, are read as legacy
+    /// timings and migrated once timings are stored again.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_timings_path(year);
+        match fs::read_to_string(&path) {
+            Ok(content) => Timings::try_from(content).map_err(|e| format!("{path}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{path}: {e}")),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,15 +154,85 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Record the environment the timings were benched on.
+    pub fn with_environment(mut self, environment: &Environment) -> Self {
+        for timing in &mut self.data {
+            timing.environment = Some(environment.clone());
+        }
+        self
+    }
+
+    /// Differences between the environments of `self` and `new`, for days present in both.
+    /// Days without a recorded environment are skipped.
+    pub fn environment_differences(&self, new: &Self) -> Vec<String> {
+        let mut differences: Vec<String> = vec![];
+
+        for timing in &new.data {
+            let stored = self
+                .data
+                .iter()
+                .find(|t| t.day == timing.day)
+                .and_then(|t| t.environment.as_ref());
+
+            if let (Some(stored), Some(current)) = (stored, &timing.environment) {
+                for difference in stored.differences(current) {
+                    if !differences.contains(&difference) {
+                        differences.push(difference);
+                    }
+                }
+            }
+        }
+
+        differences
+    }
+
+    /// Attach the heap summaries of `profiled` to the days they were recorded for.
+    pub fn with_heap(mut self, profiled: &Self) -> Self {
+        for timing in &mut self.data {
+            if let Some(profile) = profiled.data.iter().find(|t| t.day == timing.day) {
+                timing.part_1_heap = profile.part_1_heap;
+                timing.part_2_heap = profile.part_2_heap;
+            }
+        }
+        self
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Compare `new` against `self`, for every part benched in both.
+    pub fn compare(&self, new: &Self) -> Vec<PartDelta> {
+        let mut deltas = vec![];
+
+        for timing in &new.data {
+            let Some(stored) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            for part in [1, 2] {
+                if let (Some(before), Some(after)) =
+                    (stored.part_duration(part), timing.part_duration(part))
+                {
+                    deltas.push(PartDelta {
+                        day: timing.day,
+                        part,
+                        before,
+                        after,
+                    });
+                }
+            }
+        }
+
+        deltas.sort_unstable_by_key(|d| (d.day, d.part));
+        deltas
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -114,23 +287,38 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        // NOTE: legacy timings only have a mean, they are stored without statistics.
+        for (stats_key, nanos_key, timing) in [
+            ("part_1_stats", "part_1_nanos", &value.part_1),
+            ("part_2_stats", "part_2_nanos", &value.part_2),
+        ] {
+            let (stats, nanos) = match timing {
+                Some(PartTiming::Benched(stats)) => (JsonValue::from(stats), JsonValue::Null),
+                Some(PartTiming::Legacy(mean)) => {
+                    (JsonValue::Null, JsonValue::Number(mean.as_nanos() as f64))
+                }
+                None => (JsonValue::Null, JsonValue::Null),
+            };
+            map.insert(stats_key.into(), stats);
+            map.insert(nanos_key.into(), nanos);
+        }
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, heap) in [
+            ("part_1_heap", &value.part_1_heap),
+            ("part_2_heap", &value.part_2_heap),
+        ] {
+            map.insert(
+                key.into(),
+                heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "environment".into(),
+            value
+                .environment
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         JsonValue::Object(map)
@@ -151,26 +339,183 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let heap = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => HeapSummary::try_from(v).map(Some),
+        };
+
+        // NOTE: earlier versions stored only the mean, either as `"part_1_nanos": 74130` with the
+        // number of samples in `"part_1_samples": 100`, or as a display string, e.g. `"part_1": "74.13µs"`.
+        // Without the other statistics, they are taken to be the mean.
+        let part = |part: u8| {
+            if let Some(stats) = json.get(&format!("part_{part}_stats")) {
+                if !stats.is_null() {
+                    return BenchStats::try_from(stats).map(|s| Some(PartTiming::Benched(s)));
+                }
+            }
+
+            let legacy = format!("part_{part}");
+            let samples = match json.get(&format!("part_{part}_samples")) {
+                None | Some(JsonValue::Null) => None,
+                Some(JsonValue::Number(samples)) => Some(*samples as u128),
+                Some(_) => {
+                    return Err(format!(
+                        "Expected timing.part_{part}_samples to be null or a number."
+                    ))
+                }
+            };
+
+            match json.get(&format!("part_{part}_nanos")) {
+                Some(JsonValue::Number(nanos)) => {
+                    let mean = Duration::from_nanos(*nanos as u64);
+                    Ok(Some(match samples {
+                        Some(samples) => PartTiming::Benched(BenchStats {
+                            samples,
+                            ..BenchStats::single(mean)
+                        }),
+                        None => PartTiming::Legacy(mean),
+                    }))
+                }
+                Some(JsonValue::Null) => Ok(None),
+                Some(_) => Err(format!(
+                    "Expected timing.part_{part}_nanos to be null or a number."
+                )),
+                None => match json.get(&legacy) {
+                    None | Some(JsonValue::Null) => Ok(None),
+                    Some(JsonValue::String(display)) => parse_duration(display)
+                        .map(|mean| Some(PartTiming::Legacy(mean)))
+                        .ok_or(format!("Expected timing.{legacy} to be a duration.")),
+                    Some(_) => Err(format!("Expected timing.{legacy} to be null or string.")),
+                },
+            }
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1: part(1)?,
+            part_2: part(2)?,
+            part_1_heap: heap("part_1_heap")?,
+            part_2_heap: heap("part_2_heap")?,
             total_nanos,
+            environment: match json.get("environment") {
+                None | Some(JsonValue::Null) => None,
+                Some(v) => Some(Environment::try_from(v)?),
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, duration) in [
+            ("mean_nanos", value.mean),
+            ("min_nanos", value.min),
+            ("median_nanos", value.median),
+            ("p95_nanos", value.p95),
+            ("stddev_nanos", value.stddev),
+        ] {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        if let Some(allocations) = &value.allocations {
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(allocations.allocations as f64),
+            );
+            map.insert(
+                "allocated_bytes".into(),
+                JsonValue::Number(allocations.bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+        let duration = |key: &str| number(key).map(|n| Duration::from_nanos(n as u64));
+
+        Ok(BenchStats {
+            mean: duration("mean_nanos")?,
+            min: duration("min_nanos")?,
+            median: duration("median_nanos")?,
+            p95: duration("p95_nanos")?,
+            stddev: duration("stddev_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            // only recorded when built with `count-allocs`.
+            allocations: match (number("allocations"), number("allocated_bytes")) {
+                (Ok(allocations), Ok(bytes)) => Some(AllocCount {
+                    allocations: allocations as u64,
+                    bytes: bytes as u64,
+                }),
+                _ => None,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapSummary> for JsonValue {
+    fn from(value: &HeapSummary) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, number) in [
+            ("total_bytes", value.total_bytes),
+            ("peak_bytes", value.peak_bytes),
+            ("allocations", value.allocations),
+        ] {
+            map.insert(key.into(), JsonValue::Number(number as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapSummary {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as u64)
+                .ok_or(format!("Expected heap.{key} to be a number."))
+        };
+
+        Ok(HeapSummary {
+            total_bytes: number("total_bytes")?,
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
         })
     }
 }
@@ -179,48 +524,112 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(10))),
+                    part_2: Some(PartTiming::Legacy(Duration::from_millis(20))),
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 3e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(30))),
+                    part_2: Some(PartTiming::Legacy(Duration::from_millis(40))),
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 7e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(40))),
                     part_2: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 4e+10,
+                    environment: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::timings::{PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1_nanos": 1000000, "part_1_samples": 100, "part_2_nanos": null, "part_2_samples": null, "total_nanos": 1000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_duration(1), Some(Duration::from_millis(1)));
+            assert_eq!(timing.part_1.map(|t| t.samples()), Some(100));
+            assert!(timing.part_1.as_ref().and_then(PartTiming::stats).is_some());
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13µs", "part_2": "1.5s", "total_nanos": 1500074130, "part_1_stats": { "mean_nanos": 74130, "min_nanos": 70000, "median_nanos": 74000, "p95_nanos": 80000, "stddev_nanos": 1000, "samples": 100, "outliers": 0 } }] }"#;
+
+            let timings = Timings::try_from(json.to_string()).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_duration(1), Some(Duration::from_nanos(74130)));
+            assert_eq!(timing.part_1.map(|t| t.samples()), Some(100));
+            assert_eq!(
+                timing.part_2,
+                Some(PartTiming::Legacy(Duration::from_millis(1500)))
+            );
+            assert_eq!(timing.part_2.map(|t| t.samples()), Some(0));
+
+            let migrated = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            assert!(!migrated.contains(r#""part_2":"#));
+            let reparsed = Timings::try_from(migrated).unwrap();
+            assert_eq!(reparsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(reparsed.data[0].part_2, timings.data[0].part_2);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1_nanos": 1000000, "part_1_samples": 100, "part_2_nanos": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1000000, "min_nanos": 900000, "median_nanos": 950000, "p95_nanos": 1200000, "stddev_nanos": 10000, "samples": 100, "outliers": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.as_ref().and_then(PartTiming::stats).unwrap();
+            assert_eq!(stats.median, Duration::from_micros(950));
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(stats.allocations, None);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_heap() {
+            let json = r#"{ "data": [{ "day": "01", "part_1_nanos": 1000000, "part_1_samples": 100, "part_2_nanos": null, "total_nanos": 1000000, "part_1_heap": { "total_bytes": 4096, "peak_bytes": 1024, "allocations": 12 }, "part_2_heap": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let heap = timing.part_1_heap.unwrap();
+            assert_eq!(heap.peak_bytes, 1024);
+            assert_eq!(heap.allocations, 12);
+            assert_eq!(timing.part_2_heap, None);
         }
 
         #[test]
@@ -269,9 +678,11 @@ mod tests {
     }
 
     mod is_day_complete {
+        use std::time::Duration;
+
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -279,13 +690,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(1))),
+                    part_2: Some(PartTiming::Legacy(Duration::from_millis(2))),
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 3_000_000_000_f64,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +707,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(PartTiming::Legacy(Duration::from_millis(1))),
                     part_2: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 1_000_000_000_f64,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,11 +726,83 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 0.0,
+                    environment: None,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod compare {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                environment::Environment,
+                runner::BenchStats,
+                timings::{PartTiming, Timing, Timings},
+            },
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn compares_overlapping_parts() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming::Benched(BenchStats::single(
+                        Duration::from_millis(33),
+                    ))),
+                    part_2: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    total_nanos: 3.3e+7,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let deltas = timings.compare(&other);
+            assert_eq!(deltas.len(), 1);
+            assert_eq!(deltas[0].day, day!(2));
+            assert_eq!(deltas[0].before, Duration::from_millis(30));
+            assert_eq!(deltas[0].after, Duration::from_millis(33));
+            assert!((deltas[0].change_percent() - 10.0).abs() < 1e-9);
+            assert!(deltas[0].is_regression(5.0));
+            assert!(!deltas[0].is_regression(10.0));
+        }
+
+        #[test]
+        fn detects_environment_changes() {
+            let environment = Environment {
+                cpu: "Apple M1".into(),
+                cores: 8,
+                rustc: "rustc 1.83.0".into(),
+                profile: "release".into(),
+                features: vec![],
+            };
+
+            let mut stored = get_mock_timings().with_environment(&environment);
+            // days without a recorded environment are skipped.
+            stored.data[0].environment = None;
+
+            let current = get_mock_timings().with_environment(&Environment {
+                rustc: "rustc 1.84.0".into(),
+                ..environment.clone()
+            });
+            assert_eq!(
+                stored.environment_differences(&current),
+                vec!["rustc: rustc 1.83.0 -> rustc 1.84.0"]
+            );
+            assert!(stored
+                .environment_differences(&get_mock_timings())
+                .is_empty());
         }
     }
 
@@ -333,7 +822,10 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -353,7 +845,10 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);